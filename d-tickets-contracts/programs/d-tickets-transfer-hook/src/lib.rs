@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{self as instructions_sysvar, get_instruction_relative};
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        extension::{transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions},
+        state::Account as Token2022Account,
+    },
+    token_interface::{Mint, TokenAccount},
+};
+use d_tickets::{
+    cpi::accounts::CompleteTicketTransfer,
+    program::DTickets,
+    EXTRA_ACCOUNT_METAS_SEED, HOOK_AUTHORITY_SEED, PLATFORM_SEED, TICKET_MINT_AUTHORITY_SEED,
+    TICKET_SEED, TRANSFER_RECORD_SEED,
+};
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, InitializeExtraAccountMetaListInstruction};
+
+declare_id!("6GidLCXcs5hE3zFQNpYzF71qxfPTJmSpMkUoUUKUukyk");
+
+/// 转账钩子额外账户在 Execute 指令账户列表中的位置
+/// （0-4 依次为转出账户、mint、转入账户、转出方、额外账户列表）
+const D_TICKETS_PROGRAM_INDEX: u8 = 5;
+
+#[program]
+pub mod d_tickets_transfer_hook {
+    use super::*;
+
+    /// 初始化门票mint的额外账户列表（由 d-tickets 在铸造 Token-2022 门票时调用）
+    #[instruction(discriminator = InitializeExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        let mut data = ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &extra_account_metas()?)?;
+
+        msg!("转账钩子账户列表初始化成功: 门票mint: {}", ctx.accounts.mint.key());
+
+        Ok(())
+    }
+
+    /// 门票转账回调
+    ///
+    /// d-tickets 自身发起的转账（二级市场托管等）由 d-tickets 自行更新门票状态，直接放行；
+    /// 其余转账回调 d-tickets 校验持有者授权并更新门票所有者，未授权的转账将失败
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+        // 只接受 Token-2022 转账过程中的调用
+        {
+            let source = ctx.accounts.source_token_account.to_account_info();
+            let data = source.try_borrow_data()?;
+            let account = StateWithExtensions::<Token2022Account>::unpack(&data)?;
+            let extension = account.get_extension::<TransferHookAccount>()?;
+            require!(bool::from(extension.transferring), TransferHookError::NotTransferring);
+        }
+
+        let current_instruction =
+            get_instruction_relative(0, &ctx.accounts.instructions_sysvar.to_account_info())?;
+        if current_instruction.program_id == d_tickets::ID {
+            return Ok(());
+        }
+
+        let hook_authority_seeds: &[&[u8]] = &[HOOK_AUTHORITY_SEED, &[ctx.bumps.hook_authority]];
+        d_tickets::cpi::complete_ticket_transfer(CpiContext::new_with_signer(
+            ctx.accounts.d_tickets_program.to_account_info(),
+            CompleteTicketTransfer {
+                hook_authority: ctx.accounts.hook_authority.to_account_info(),
+                ticket_mint: ctx.accounts.mint.to_account_info(),
+                source_token_account: ctx.accounts.source_token_account.to_account_info(),
+                destination_token_account: ctx.accounts.destination_token_account.to_account_info(),
+                ticket: ctx.accounts.ticket.to_account_info(),
+                transfer_record: ctx.accounts.transfer_record.to_account_info(),
+                platform: ctx.accounts.platform.to_account_info(),
+            },
+            &[hook_authority_seeds],
+        ))
+    }
+}
+
+/// 门票转账需要的额外账户：d-tickets 程序、门票账户、转让记录、钩子权限PDA、指令sysvar、平台账户
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&d_tickets::ID, false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            D_TICKETS_PROGRAM_INDEX,
+            &[
+                Seed::Literal { bytes: TICKET_SEED.to_vec() },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            D_TICKETS_PROGRAM_INDEX,
+            &[
+                Seed::Literal { bytes: TRANSFER_RECORD_SEED.to_vec() },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: HOOK_AUTHORITY_SEED.to_vec() }],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_pubkey(&instructions_sysvar::ID, false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            D_TICKETS_PROGRAM_INDEX,
+            &[Seed::Literal { bytes: PLATFORM_SEED.to_vec() }],
+            false,
+            false,
+        )?,
+    ])
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: 额外账户列表，按 spl-transfer-hook-interface 的TLV格式写入
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// d-tickets 的门票铸造权限PDA（同时是门票mint的转账钩子权限）
+    #[account(
+        seeds = [TICKET_MINT_AUTHORITY_SEED],
+        bump,
+        seeds::program = d_tickets::ID
+    )]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: 转出方（钱包或代理人），由 Token-2022 校验
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: 额外账户列表
+    #[account(
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub d_tickets_program: Program<'info, DTickets>,
+
+    /// CHECK: 门票账户，由 d-tickets 校验
+    #[account(mut)]
+    pub ticket: UncheckedAccount<'info>,
+
+    /// CHECK: 转让记录账户，由 d-tickets 校验
+    #[account(mut)]
+    pub transfer_record: UncheckedAccount<'info>,
+
+    /// CHECK: 钩子权限PDA，仅用于签名回调 d-tickets
+    #[account(
+        seeds = [HOOK_AUTHORITY_SEED],
+        bump
+    )]
+    pub hook_authority: UncheckedAccount<'info>,
+
+    /// CHECK: 指令sysvar，用于识别 d-tickets 自身发起的转账
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// CHECK: 平台账户，由 d-tickets 校验是否暂停
+    pub platform: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum TransferHookError {
+    #[msg("仅能在代币转账过程中调用")]
+    NotTransferring,
+}
@@ -1,13 +1,34 @@
+use anchor_lang::prelude::*;
+
 /// 程序种子常量
 pub const PLATFORM_SEED: &[u8] = b"platform";
 pub const EVENT_SEED: &[u8] = b"event";
 pub const TICKET_TYPE_SEED: &[u8] = b"ticket_type";
 pub const TICKET_SEED: &[u8] = b"ticket";
 pub const TICKET_MINT_SEED: &[u8] = b"ticket_mint";
+pub const TICKET_MINT_AUTHORITY_SEED: &[u8] = b"ticket_mint_authority";
 pub const EARNINGS_SEED: &[u8] = b"earnings";
+pub const VAULT_SEED: &[u8] = b"vault";
 pub const MARKETPLACE_LISTING_SEED: &[u8] = b"marketplace_listing";
 pub const MARKETPLACE_TRANSACTION_SEED: &[u8] = b"marketplace_transaction";
+pub const MARKETPLACE_OFFER_SEED: &[u8] = b"marketplace_offer";
 pub const TRANSFER_RECORD_SEED: &[u8] = b"transfer_record";
+pub const PURCHASE_RECORD_SEED: &[u8] = b"purchase_record";
+pub const SEAT_HOLD_SEED: &[u8] = b"seat_hold";
+pub const SEAT_STATUS_PAGE_SEED: &[u8] = b"seat_status_page";
+pub const SEAT_LAYOUT_SEED: &[u8] = b"seat_layout";
+pub const SEAT_PRICE_TIERS_SEED: &[u8] = b"seat_price_tiers";
+pub const HOOK_AUTHORITY_SEED: &[u8] = b"hook_authority";
+
+/// Token Metadata 程序的PDA种子
+pub const METADATA_SEED: &[u8] = b"metadata";
+pub const EDITION_SEED: &[u8] = b"edition";
+
+/// 转账钩子额外账户列表的PDA种子（spl-transfer-hook-interface 约定）
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+/// 门票转账钩子程序（Token-2022 门票的转账回调至本程序）
+pub const TRANSFER_HOOK_PROGRAM_ID: Pubkey = pubkey!("6GidLCXcs5hE3zFQNpYzF71qxfPTJmSpMkUoUUKUukyk");
 
 /// 业务常量
 pub const MAX_EVENT_NAME_LENGTH: usize = 100;
@@ -20,15 +41,23 @@ pub const IPFS_HASH_LENGTH: usize = 46;
 
 /// 票务限制常量
 pub const MAX_TICKET_TYPES_PER_EVENT: u8 = 10;
-pub const MAX_TICKETS_PER_PURCHASE: u32 = 10;
+pub const MAX_TICKETS_PER_PURCHASE: u32 = 3; // 受交易大小限制：除固定账户外每张门票需5个账户，4张时超出1232字节上限
+pub const MAX_SEATS_PER_HOLD: usize = 10; // 与 SeatHold.seat_indices 的 max_len 保持一致
+pub const SEATS_PER_BITMAP_PAGE: u32 = 16_000; // 每个座位状态分页容纳的座位数
+pub const MAX_SEATS_PER_AREA: u32 = 100_000; // 单个区域最多座位数（7个分页）
+pub const MAX_SEATS_PER_CONFIGURE_BATCH: usize = 10; // 批量配置座位时单笔交易的座位数上限
+pub const MAX_SEAT_PRICE_TIERS: usize = 20; // 与 SeatPriceTierAccount.tiers 的 max_len 保持一致
 pub const MIN_TICKET_PRICE: u64 = 1_000_000; // 0.001 SOL
 pub const MAX_TICKET_PRICE: u64 = 1_000_000_000_000; // 1000 SOL
+pub const MAX_SUPPORTED_TOKENS: usize = 5; // 与 PlatformAccount.supported_tokens 的 max_len 保持一致
 
 /// 手续费常量（基点）
 pub const MAX_PLATFORM_FEE_BPS: u16 = 1000; // 10%
 pub const MAX_ROYALTY_BPS: u16 = 2500; // 25%
 pub const DEFAULT_PLATFORM_FEE_BPS: u16 = 250; // 2.5%
+pub const MAX_REFUND_FEE_BPS: u16 = 5000; // 退票手续费最高50%
 pub const BASIS_POINTS_DIVISOR: u64 = 10000;
+pub const MAX_SEAT_PRICE_MULTIPLIER_BPS: u32 = 50_000; // 座位价格档位最高为票种价格的5倍
 
 /// 默认收款地址（可以在运行时修改）
 pub const DEFAULT_FEE_RECIPIENT: &str = "4RmJgJPUEkBJu8etoeMSt6B62RGvMR7iviNQEyHThJHG"; // System Program地址作为占位符
@@ -38,6 +67,8 @@ pub const MIN_SALE_DURATION: i64 = 3600; // 1小时
 pub const MAX_SALE_DURATION: i64 = 365 * 24 * 3600; // 1年
 pub const MIN_EVENT_NOTICE: i64 = 24 * 3600; // 24小时
 pub const DEFAULT_LISTING_DURATION: i64 = 30 * 24 * 3600; // 30天
+pub const DEFAULT_SEAT_HOLD_DURATION: i64 = 300; // 选座锁定默认5分钟
+pub const MAX_SEAT_HOLD_DURATION: i64 = 900; // 选座锁定最长15分钟
 
 /// 动态定价常量
 pub const MAX_PRICE_INCREASE_BPS: i16 = 5000; // 最大涨价50%
@@ -47,6 +78,8 @@ pub const PRICING_UPDATE_INTERVAL: i64 = 3600; // 价格更新间隔1小时
 /// NFT相关常量
 pub const NFT_SYMBOL: &str = "DTIX";
 pub const NFT_CREATOR_ROYALTY_BPS: u16 = 500; // 5%
+pub const NFT_METADATA_URI_PREFIX: &str = "https://ipfs.io/ipfs/";
+pub const MAX_NFT_NAME_LENGTH: usize = 32; // Token Metadata 名称上限（字节）
 
 /// 账户discriminator偏移量
 pub const DISCRIMINATOR_LENGTH: usize = 8;
@@ -118,6 +118,15 @@ pub enum TicketError {
     #[msg("支付金额过多")]
     ExcessivePayment,
 
+    #[msg("不支持的支付代币")]
+    UnsupportedPaymentToken,
+
+    #[msg("支付代币已在支持列表中")]
+    PaymentTokenAlreadySupported,
+
+    #[msg("支付代币与活动结算币种不一致")]
+    InvalidPaymentMint,
+
     // ===== 退票相关错误 =====
     #[msg("不符合退票条件")]
     RefundNotAllowed,
@@ -143,10 +152,34 @@ pub enum TicketError {
     
     #[msg("不能购买自己的挂单")]
     CannotBuyOwnListing,
+
+    #[msg("挂单到期时间无效")]
+    InvalidListingExpiry,
+
+    #[msg("挂单尚未过期")]
+    ListingNotExpired,
+
+    #[msg("出价已过期")]
+    OfferExpired,
+
+    #[msg("出价尚未过期")]
+    OfferNotExpired,
+
+    #[msg("出价到期时间无效")]
+    InvalidOfferExpiry,
+
+    #[msg("门票与出价指定的门票不一致")]
+    OfferTicketMismatch,
     
     #[msg("版税比例无效")]
     InvalidRoyaltyRate,
 
+    #[msg("转售价格超出主办方设定的范围")]
+    ResalePriceOutOfBounds,
+
+    #[msg("门票转让未经持有者授权")]
+    TransferNotApproved,
+
     // ===== 权限相关错误 =====
     #[msg("非活动主办方")]
     NotEventOrganizer,
@@ -316,6 +349,45 @@ pub enum TicketError {
     #[msg("存在已售座位，不能删除座位状态映射")]
     CannotDeleteWithSoldSeats,
 
+    #[msg("座位状态映射已是最新的位图编码")]
+    SeatStatusMapAlreadyMigrated,
+
+    #[msg("座位布局无效")]
+    InvalidSeatLayout,
+
+    #[msg("该区域已登记座位布局，需要提供座位布局账户")]
+    SeatLayoutRequired,
+
+    #[msg("座位信息与链上座位布局不一致")]
+    SeatInfoMismatch,
+
+    #[msg("座位已关联座位状态映射，状态以位图为准，请通过座位状态映射更新")]
+    SeatManagedByStatusMap,
+
+    #[msg("区域未关联到该活动的票种")]
+    AreaNotMappedToEvent,
+
+    #[msg("座位价格档位无效")]
+    InvalidSeatPriceTier,
+
+    #[msg("该区域已设置座位价格档位，需要提供价格档位账户")]
+    SeatPriceTiersRequired,
+
+    #[msg("座位位于分页中，需要提供对应的座位状态分页账户")]
+    SeatStatusPageRequired,
+
+    #[msg("座位锁定时长无效")]
+    InvalidHoldDuration,
+
+    #[msg("座位锁定已过期")]
+    SeatHoldExpired,
+
+    #[msg("座位锁定尚未过期")]
+    SeatHoldNotExpired,
+
+    #[msg("座位未被当前锁定持有")]
+    SeatNotHeld,
+
     // ===== 场馆删除相关错误 =====
     #[msg("非场馆拥有者")]
     NotVenueOwner,
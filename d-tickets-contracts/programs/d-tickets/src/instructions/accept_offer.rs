@@ -0,0 +1,256 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Token, TokenAccount},
+    token_interface::{Mint, TokenAccount as TicketTokenAccount, TokenInterface},
+};
+use crate::{
+    state::{MarketplaceOfferAccount, TicketAccount, TicketStatus, TicketTypeAccount, OrganizerEarnings, PlatformAccount},
+    error::TicketError,
+    constants::*,
+    utils::{calculate_fee, transfer_ticket_nft, Settlement},
+};
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    #[account(
+        mut,
+        seeds = [MARKETPLACE_OFFER_SEED, offer.buyer.as_ref(), offer.ticket_type.as_ref(), &offer.offer_id.to_le_bytes()],
+        bump = offer.bump,
+        close = buyer
+    )]
+    pub offer: Box<Account<'info, MarketplaceOfferAccount>>,
+
+    #[account(
+        mut,
+        seeds = [TICKET_SEED, ticket.mint.as_ref()],
+        bump = ticket.bump
+    )]
+    pub ticket: Box<Account<'info, TicketAccount>>,
+
+    #[account(
+        seeds = [TICKET_TYPE_SEED, ticket.event.as_ref(), ticket.ticket_type_name.as_bytes()],
+        bump = ticket_type.bump,
+        constraint = ticket_type.key() == offer.ticket_type @ TicketError::OfferTicketMismatch
+    )]
+    pub ticket_type: Box<Account<'info, TicketTypeAccount>>,
+
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, ticket.event.as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Box<Account<'info, OrganizerEarnings>>,
+
+    /// 活动资金托管账户（PDA，接收版税）
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, ticket.event.as_ref(), VAULT_SEED],
+        bump
+    )]
+    pub event_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Box<Account<'info, PlatformAccount>>,
+
+    /// CHECK: 平台收款账户，地址必须与 platform.fee_recipient 一致
+    #[account(
+        mut,
+        address = platform.fee_recipient @ TicketError::InvalidAccount
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: 出价买家，地址必须与出价一致，接收门票和返还的租金
+    #[account(
+        mut,
+        address = offer.buyer @ TicketError::InvalidAccount
+    )]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// 门票NFT的mint
+    #[account(address = ticket.mint @ TicketError::InvalidAccount)]
+    pub ticket_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 卖家（门票持有者）的门票代币账户
+    #[account(
+        mut,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = ticket_token_program
+    )]
+    pub seller_token_account: Box<InterfaceAccount<'info, TicketTokenAccount>>,
+
+    /// 买家的门票代币账户（不存在时由卖家创建）
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = ticket_token_program
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, TicketTokenAccount>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// 出价托管代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub escrow_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 买家的支付代币账户（SPL代币结算时必填，接收托管账户中的剩余代币）
+    #[account(mut)]
+    pub buyer_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 卖家的收款代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub seller_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 活动的SPL代币托管账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 平台收款代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub fee_recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 支付结算使用的代币程序
+    pub token_program: Program<'info, Token>,
+    /// 门票NFT所属的代币程序（SPL Token 或 Token-2022）
+    pub ticket_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// 门票持有者接受出价：托管的出价金额支付给卖家（扣除版税和平台手续费），门票转给买家
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, AcceptOffer<'info>>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    let ticket = &mut ctx.accounts.ticket;
+    let earnings = &mut ctx.accounts.earnings;
+    let platform = &mut ctx.accounts.platform;
+    let clock = Clock::get()?;
+
+    // 验证出价未过期，且门票与出价指定的门票一致
+    require!(
+        !offer.is_expired(clock.unix_timestamp),
+        TicketError::OfferExpired
+    );
+    if let Some(ticket_mint) = offer.ticket_mint {
+        require_keys_eq!(ticket.mint, ticket_mint, TicketError::OfferTicketMismatch);
+    }
+
+    // 验证卖家持有门票（门票账户记录的所有者且持有门票NFT）
+    require!(
+        ticket.current_owner == ctx.accounts.seller.key(),
+        TicketError::NotTicketOwner
+    );
+    require!(
+        ctx.accounts.seller_token_account.amount == 1,
+        TicketError::NotTicketOwner
+    );
+    require!(
+        offer.buyer != ctx.accounts.seller.key(),
+        TicketError::CannotBuyOwnListing
+    );
+
+    // 验证门票状态（已核销、已退款或已挂单的门票不能成交）和转售价格范围
+    require!(
+        ticket.current_status == TicketStatus::Sold,
+        TicketError::InvalidTicketStatus
+    );
+    require!(
+        ticket.transferable,
+        TicketError::TicketNotTransferable
+    );
+    let price = offer.price;
+    ctx.accounts.ticket_type.validate_resale_price(ticket.original_price, price)?;
+
+    // 计算费用（版税按票种设定，平台手续费按当前费率）
+    let platform_fee = calculate_fee(price, platform.platform_fee_bps)?;
+    let royalty_fee = calculate_fee(price, ctx.accounts.ticket_type.max_resale_royalty)?;
+    let seller_amount = price
+        .checked_sub(platform_fee)
+        .and_then(|amount| amount.checked_sub(royalty_fee))
+        .ok_or(TicketError::FeeCalculationError)?;
+
+    // 从出价托管付款：卖家所得、版税进入活动托管账户、平台手续费
+    let settlement = Settlement::new(
+        offer.payment_mint,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    );
+    let escrow = settlement.party(
+        offer.to_account_info(),
+        ctx.accounts.escrow_token_account.as_deref(),
+    )?;
+    let seller = settlement.party(
+        ctx.accounts.seller.to_account_info(),
+        ctx.accounts.seller_payment_account.as_deref(),
+    )?;
+    let event_vault = settlement.party(
+        ctx.accounts.event_vault.to_account_info(),
+        ctx.accounts.vault_token_account.as_deref(),
+    )?;
+    let fee_recipient = settlement.party(
+        ctx.accounts.fee_recipient.to_account_info(),
+        ctx.accounts.fee_recipient_token_account.as_deref(),
+    )?;
+    let buyer = settlement.party(
+        ctx.accounts.buyer.to_account_info(),
+        ctx.accounts.buyer_payment_account.as_deref(),
+    )?;
+    let offer_id_bytes = offer.offer_id.to_le_bytes();
+    let offer_seeds: &[&[u8]] = &[
+        MARKETPLACE_OFFER_SEED,
+        offer.buyer.as_ref(),
+        offer.ticket_type.as_ref(),
+        &offer_id_bytes,
+        &[offer.bump],
+    ];
+    settlement.transfer_from_escrow(&escrow, &seller, offer_seeds, seller_amount)?;
+    settlement.transfer_from_escrow(&escrow, &event_vault, offer_seeds, royalty_fee)?;
+    settlement.transfer_from_escrow(&escrow, &fee_recipient, offer_seeds, platform_fee)?;
+    settlement.close_escrow(&escrow, &buyer, ctx.accounts.buyer.to_account_info(), offer_seeds)?;
+
+    // 门票NFT由卖家转给买家（出价账户在指令结束时关闭，租金退还买家）
+    transfer_ticket_nft(
+        &ctx.accounts.ticket_token_program.to_account_info(),
+        ctx.accounts.seller_token_account.to_account_info(),
+        ctx.accounts.ticket_mint.to_account_info(),
+        ctx.accounts.buyer_token_account.to_account_info(),
+        ctx.accounts.seller.to_account_info(),
+        ctx.remaining_accounts,
+        &[],
+    )?;
+
+    // 更新门票所有者
+    ticket.current_owner = offer.buyer;
+    ticket.transfer_count += 1;
+    ticket.last_transfer_at = Some(clock.unix_timestamp);
+
+    // 更新收益
+    earnings.royalty_earnings += royalty_fee;
+    earnings.total_earnings += royalty_fee;
+    earnings.pending_amount += royalty_fee;
+    platform.total_platform_revenue = platform.total_platform_revenue
+        .checked_add(platform_fee)
+        .ok_or(TicketError::Overflow)?;
+    platform.total_transactions = platform.total_transactions
+        .checked_add(1)
+        .ok_or(TicketError::Overflow)?;
+
+    msg!(
+        "出价成交: 门票mint: {}, 价格: {}, 卖家所得: {}, 版税: {}, 平台手续费: {}",
+        ticket.mint,
+        price,
+        seller_amount,
+        royalty_fee,
+        platform_fee
+    );
+
+    Ok(())
+}
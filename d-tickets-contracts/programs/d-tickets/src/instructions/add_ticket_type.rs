@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::{constants::*, error::TicketError, state::*};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address, AssociatedToken, Create},
+    token::{Mint, Token},
+};
+use crate::{constants::*, error::TicketError, state::*, utils::fund_rent_exemption};
 
 #[derive(Accounts)]
 #[instruction(type_name: String)]
@@ -15,21 +19,46 @@ pub struct AddTicketType<'info> {
     #[account(
         init,
         payer = organizer,
-        space = TicketTypeAccount::INIT_SPACE,
+        space = 8 + TicketTypeAccount::INIT_SPACE,
         seeds = [TICKET_TYPE_SEED, event.key().as_ref(), type_name.as_bytes()],
         bump
     )]
     pub ticket_type: Account<'info, TicketTypeAccount>,
     
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Account<'info, OrganizerEarnings>,
+
     #[account(
         seeds = [PLATFORM_SEED],
-        bump = platform.bump
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
     )]
     pub platform: Account<'info, PlatformAccount>,
+
+    /// 活动资金托管账户（PDA，SPL代币托管账户的持有者）
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref(), VAULT_SEED],
+        bump
+    )]
+    pub event_vault: SystemAccount<'info>,
+
+    /// 定价代币mint（为空时以SOL定价）
+    pub payment_mint: Option<Account<'info, Mint>>,
+
+    /// CHECK: 活动的SPL代币托管账户（event_vault 的关联代币账户），不存在时自动创建
+    #[account(mut)]
+    pub vault_token_account: Option<UncheckedAccount<'info>>,
     
     #[account(mut)]
     pub organizer: Signer<'info>,
     
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
@@ -44,6 +73,7 @@ pub fn handler(
 ) -> Result<()> {
     let event = &mut ctx.accounts.event;
     let ticket_type = &mut ctx.accounts.ticket_type;
+    let earnings = &mut ctx.accounts.earnings;
     let platform = &ctx.accounts.platform;
     let current_time = Clock::get()?.unix_timestamp;
 
@@ -73,16 +103,68 @@ pub fn handler(
         TicketError::ExceedsMaxTicketTypes
     );
 
+    // 验证定价代币：必须是平台支持的代币，且同一活动的所有票种使用同一结算币种
+    let payment_mint = ctx.accounts.payment_mint.as_ref().map(|mint| mint.key());
+    if let Some(mint) = payment_mint {
+        require!(
+            platform.is_supported_token(&mint),
+            TicketError::UnsupportedPaymentToken
+        );
+    }
+    if event.ticket_types_count == 0 {
+        earnings.payment_mint = payment_mint;
+    } else {
+        require!(
+            earnings.payment_mint == payment_mint,
+            TicketError::InvalidPaymentMint
+        );
+    }
+
+    // 由主办方预存托管账户的免租余额，不计入活动收益，首笔小额入账不会因租金不足失败
+    fund_rent_exemption(
+        ctx.accounts.organizer.to_account_info(),
+        ctx.accounts.event_vault.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // SPL代币定价时创建活动的代币托管账户
+    if let Some(mint) = &ctx.accounts.payment_mint {
+        let vault_token_account = ctx.accounts.vault_token_account
+            .as_ref()
+            .ok_or(TicketError::InvalidAccount)?;
+        require_keys_eq!(
+            vault_token_account.key(),
+            get_associated_token_address(&ctx.accounts.event_vault.key(), &mint.key()),
+            TicketError::InvalidAccount
+        );
+        associated_token::create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            Create {
+                payer: ctx.accounts.organizer.to_account_info(),
+                associated_token: vault_token_account.to_account_info(),
+                authority: ctx.accounts.event_vault.to_account_info(),
+                mint: mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+    }
+
     // 设置票种账户并保存bump值
     ticket_type.event = event.key();
     ticket_type.ticket_type_id = event.ticket_types_count;
     ticket_type.type_name = type_name;
     ticket_type.initial_price = initial_price;
     ticket_type.current_price = initial_price;
+    ticket_type.payment_mint = payment_mint;
     ticket_type.total_supply = total_supply;
     ticket_type.sold_count = 0;
     ticket_type.refunded_count = 0;
     ticket_type.max_resale_royalty = max_resale_royalty;
+    ticket_type.refund_fee_bps = 0;
+    ticket_type.max_per_wallet = 0;
+    ticket_type.max_resale_markup_bps = 0;
+    ticket_type.min_resale_price_bps = 0;
     ticket_type.is_fixed_price = is_fixed_price;
     ticket_type.dynamic_pricing_rules_hash = dynamic_pricing_rules_hash;
     ticket_type.last_price_update = current_time;
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{Token, TokenAccount},
+    token_2022,
+    token_interface::Mint,
+};
+use crate::{
+    state::{TicketAccount, TicketStatus, TicketTypeAccount, OrganizerEarnings, TicketTransferRecord, TransferType, PlatformAccount},
+    error::TicketError,
+    constants::*,
+    utils::{calculate_fee, Settlement},
+};
+
+#[derive(Accounts)]
+pub struct ApproveTicketTransfer<'info> {
+    #[account(
+        seeds = [TICKET_SEED, ticket.mint.as_ref()],
+        bump = ticket.bump
+    )]
+    pub ticket: Box<Account<'info, TicketAccount>>,
+
+    #[account(
+        seeds = [TICKET_TYPE_SEED, ticket.event.as_ref(), ticket.ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Box<Account<'info, TicketTypeAccount>>,
+
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, ticket.event.as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Box<Account<'info, OrganizerEarnings>>,
+
+    /// 活动资金托管账户（PDA，接收版税）
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, ticket.event.as_ref(), VAULT_SEED],
+        bump
+    )]
+    pub event_vault: SystemAccount<'info>,
+
+    /// 门票NFT的mint（仅 Token-2022 门票经由转账钩子转让）
+    #[account(
+        address = ticket.mint @ TicketError::InvalidAccount,
+        owner = token_2022::ID @ TicketError::InvalidAccount
+    )]
+    pub ticket_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + TicketTransferRecord::INIT_SPACE,
+        seeds = [TRANSFER_RECORD_SEED, ticket.mint.as_ref()],
+        bump
+    )]
+    pub transfer_record: Box<Account<'info, TicketTransferRecord>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// 持有者的支付代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub owner_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 活动的SPL代币托管账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Box<Account<'info, PlatformAccount>>,
+}
+
+pub fn handler(
+    ctx: Context<ApproveTicketTransfer>,
+    recipient: Pubkey,
+    declared_price: u64,
+) -> Result<()> {
+    let ticket = &ctx.accounts.ticket;
+    let earnings = &mut ctx.accounts.earnings;
+    let transfer_record = &mut ctx.accounts.transfer_record;
+    let owner_key = ctx.accounts.owner.key();
+
+    // 验证门票状态和转让权限
+    require!(
+        ticket.transferable,
+        TicketError::TicketNotTransferable
+    );
+    require!(
+        ticket.current_status == TicketStatus::Sold,
+        TicketError::InvalidTicketStatus
+    );
+    require!(
+        ticket.current_owner == owner_key,
+        TicketError::NotTicketOwner
+    );
+    require!(
+        recipient != owner_key && recipient != Pubkey::default(),
+        TicketError::InvalidArgument
+    );
+
+    // 场外转让的申报价格同样受转售价格上下限约束
+    ctx.accounts.ticket_type.validate_resale_price(ticket.original_price, declared_price)?;
+
+    // 版税按申报价格与原价中的较高者计算，防止以低价申报规避版税
+    let royalty_base = declared_price.max(ticket.original_price);
+    let royalty_fee = calculate_fee(royalty_base, ctx.accounts.ticket_type.max_resale_royalty)?;
+
+    // 同一持有者尚未完成的授权只补交差额，仅修改接收方时不重复收取
+    let is_pending = transfer_record.ticket == ticket.key()
+        && transfer_record.from == owner_key
+        && transfer_record.transferred_at == 0;
+    let royalty_due = if is_pending {
+        royalty_fee.saturating_sub(transfer_record.royalty_amount)
+    } else {
+        royalty_fee
+    };
+
+    // 持有者按活动结算币种向活动托管账户支付版税
+    let settlement = Settlement::new(
+        earnings.payment_mint,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    );
+    let owner = settlement.party(
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_payment_account.as_deref(),
+    )?;
+    let event_vault = settlement.party(
+        ctx.accounts.event_vault.to_account_info(),
+        ctx.accounts.vault_token_account.as_deref(),
+    )?;
+    settlement.require_balance(&owner, royalty_due)?;
+    settlement.transfer(&owner, &event_vault, royalty_due)?;
+
+    // 记录待完成的转让，transferred_at 为0表示尚未转账，由转账钩子回调时完成
+    transfer_record.ticket = ticket.key();
+    transfer_record.from = owner_key;
+    transfer_record.to = recipient;
+    transfer_record.price = declared_price;
+    transfer_record.royalty_amount = if is_pending {
+        transfer_record.royalty_amount.max(royalty_fee)
+    } else {
+        royalty_fee
+    };
+    transfer_record.platform_fee = 0;
+    transfer_record.transferred_at = 0;
+    transfer_record.transfer_type = TransferType::Gift;
+
+    // 更新收益
+    earnings.royalty_earnings += royalty_due;
+    earnings.total_earnings += royalty_due;
+    earnings.pending_amount += royalty_due;
+
+    msg!(
+        "门票转让已授权: 门票mint: {}, 接收方: {}, 版税: {}",
+        ticket.mint,
+        recipient,
+        royalty_due
+    );
+
+    Ok(())
+}
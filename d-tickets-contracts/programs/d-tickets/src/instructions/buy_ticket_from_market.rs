@@ -1,8 +1,14 @@
 use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Token, TokenAccount},
+    token_interface::{self, CloseAccount, Mint, TokenAccount as TicketTokenAccount, TokenInterface},
+};
 use crate::{
-    state::{MarketplaceListingAccount, TicketAccount, TicketTypeAccount, OrganizerEarnings, PlatformAccount, ListingStatus},
+    state::{MarketplaceListingAccount, TicketAccount, TicketStatus, TicketTypeAccount, OrganizerEarnings, PlatformAccount, ListingStatus},
     error::TicketError,
     constants::*,
+    utils::{calculate_fee, transfer_ticket_nft, Settlement},
 };
 
 #[derive(Accounts)]
@@ -11,94 +17,227 @@ pub struct BuyTicketFromMarket<'info> {
         mut,
         seeds = [MARKETPLACE_LISTING_SEED, listing.ticket_mint.as_ref()],
         bump = listing.bump,
-        constraint = listing.status == ListingStatus::Active @ TicketError::ListingNotFound
+        constraint = listing.status == ListingStatus::Active @ TicketError::ListingNotFound,
+        close = seller
     )]
-    pub listing: Account<'info, MarketplaceListingAccount>,
+    pub listing: Box<Account<'info, MarketplaceListingAccount>>,
 
     #[account(
         mut,
         seeds = [TICKET_SEED, ticket.mint.as_ref()],
-        bump = ticket.bump
+        bump = ticket.bump,
+        constraint = ticket.mint == listing.ticket_mint @ TicketError::InvalidAccount
     )]
-    pub ticket: Account<'info, TicketAccount>,
+    pub ticket: Box<Account<'info, TicketAccount>>,
 
     #[account(
-        mut,
         seeds = [TICKET_TYPE_SEED, ticket.event.as_ref(), ticket.ticket_type_name.as_bytes()],
         bump = ticket_type.bump
     )]
-    pub ticket_type: Account<'info, TicketTypeAccount>,
+    pub ticket_type: Box<Account<'info, TicketTypeAccount>>,
 
     #[account(
         mut,
         seeds = [EARNINGS_SEED, ticket.event.as_ref()],
         bump = earnings.bump
     )]
-    pub earnings: Account<'info, OrganizerEarnings>,
+    pub earnings: Box<Account<'info, OrganizerEarnings>>,
+
+    /// 活动资金托管账户（PDA，接收版税）
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, ticket.event.as_ref(), VAULT_SEED],
+        bump
+    )]
+    pub event_vault: SystemAccount<'info>,
 
     #[account(
+        mut,
         seeds = [PLATFORM_SEED],
-        bump = platform.bump
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Box<Account<'info, PlatformAccount>>,
+
+    /// CHECK: 平台收款账户，地址必须与 platform.fee_recipient 一致
+    #[account(
+        mut,
+        address = platform.fee_recipient @ TicketError::InvalidAccount
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: 卖家钱包，地址必须与挂单卖家一致
+    #[account(
+        mut,
+        address = listing.seller @ TicketError::InvalidAccount
+    )]
+    pub seller: UncheckedAccount<'info>,
+
+    /// 门票NFT的mint
+    #[account(address = listing.ticket_mint @ TicketError::InvalidAccount)]
+    pub ticket_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 挂单托管代币账户
+    #[account(
+        mut,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = ticket_token_program
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TicketTokenAccount>>,
+
+    /// 买家的门票代币账户
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = ticket_token_program
     )]
-    pub platform: Account<'info, PlatformAccount>,
+    pub buyer_token_account: Box<InterfaceAccount<'info, TicketTokenAccount>>,
 
     #[account(mut)]
     pub buyer: Signer<'info>,
+
+    /// 买家的支付代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub buyer_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 卖家的收款代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub seller_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 活动的SPL代币托管账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 平台收款代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub fee_recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 支付结算使用的代币程序
+    pub token_program: Program<'info, Token>,
+    /// 门票NFT所属的代币程序（SPL Token 或 Token-2022）
+    pub ticket_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<BuyTicketFromMarket>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BuyTicketFromMarket<'info>>) -> Result<()> {
     let listing = &mut ctx.accounts.listing;
     let ticket = &mut ctx.accounts.ticket;
-    let ticket_type = &mut ctx.accounts.ticket_type;
     let earnings = &mut ctx.accounts.earnings;
-    let platform = &ctx.accounts.platform;
+    let platform = &mut ctx.accounts.platform;
     let clock = Clock::get()?;
 
+    // 验证挂单未过期
+    require!(
+        !listing.is_expired(clock.unix_timestamp),
+        TicketError::ListingExpired
+    );
+
     // 验证买家不是卖家
     require!(
         listing.seller != ctx.accounts.buyer.key(),
         TicketError::CannotBuyOwnListing
     );
 
-    // 计算费用
+    // 按挂单时锁定的费率计算费用
     let price = listing.price;
-    let platform_fee = price
-        .checked_mul(platform.platform_fee_bps as u64)
-        .unwrap()
-        .checked_div(10000)
-        .unwrap();
-    
-    let royalty_fee = price
-        .checked_mul(ticket_type.max_resale_royalty as u64)
-        .unwrap()
-        .checked_div(10000)
-        .unwrap();
-
-    let _seller_amount = price
+    let platform_fee = calculate_fee(price, listing.platform_fee_bps)?;
+    let royalty_fee = calculate_fee(price, listing.royalty_bps)?;
+    let seller_amount = price
         .checked_sub(platform_fee)
-        .unwrap()
-        .checked_sub(royalty_fee)
-        .unwrap();
+        .and_then(|amount| amount.checked_sub(royalty_fee))
+        .ok_or(TicketError::FeeCalculationError)?;
 
-    // 验证支付金额
-    require!(
-        ctx.accounts.buyer.lamports() >= price,
-        TicketError::InsufficientPayment
+    // 按活动结算币种（SOL或SPL代币）付款
+    let settlement = Settlement::new(
+        earnings.payment_mint,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
     );
+    let buyer = settlement.party(
+        ctx.accounts.buyer.to_account_info(),
+        ctx.accounts.buyer_payment_account.as_deref(),
+    )?;
+    let seller = settlement.party(
+        ctx.accounts.seller.to_account_info(),
+        ctx.accounts.seller_payment_account.as_deref(),
+    )?;
+    let event_vault = settlement.party(
+        ctx.accounts.event_vault.to_account_info(),
+        ctx.accounts.vault_token_account.as_deref(),
+    )?;
+    let fee_recipient = settlement.party(
+        ctx.accounts.fee_recipient.to_account_info(),
+        ctx.accounts.fee_recipient_token_account.as_deref(),
+    )?;
+
+    // 验证支付金额
+    settlement.require_balance(&buyer, price)?;
 
-    // 更新挂单状态
+    // 买家付款：卖家所得、版税进入活动托管账户、平台手续费
+    settlement.transfer(&buyer, &seller, seller_amount)?;
+    settlement.transfer(&buyer, &event_vault, royalty_fee)?;
+    settlement.transfer(&buyer, &fee_recipient, platform_fee)?;
+
+    // 门票NFT从托管账户转给买家，并关闭托管账户（租金退还卖家）
+    let listing_seeds: &[&[u8]] = &[
+        MARKETPLACE_LISTING_SEED,
+        listing.ticket_mint.as_ref(),
+        &[listing.bump],
+    ];
+    transfer_ticket_nft(
+        &ctx.accounts.ticket_token_program.to_account_info(),
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.ticket_mint.to_account_info(),
+        ctx.accounts.buyer_token_account.to_account_info(),
+        listing.to_account_info(),
+        ctx.remaining_accounts,
+        &[listing_seeds],
+    )?;
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.ticket_token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_token_account.to_account_info(),
+            destination: ctx.accounts.seller.to_account_info(),
+            authority: listing.to_account_info(),
+        },
+        &[listing_seeds],
+    ))?;
+
+    // 更新挂单状态（挂单账户在指令结束时关闭，租金退还卖家）
     listing.status = ListingStatus::Sold;
     listing.buyer = Some(ctx.accounts.buyer.key());
     listing.sold_at = Some(clock.unix_timestamp);
     listing.sold_price = Some(price);
 
-    // 更新门票所有者
+    // 更新门票所有者，门票恢复为有效状态
     ticket.current_owner = ctx.accounts.buyer.key();
+    ticket.current_status = TicketStatus::Sold;
     ticket.transfer_count += 1;
     ticket.last_transfer_at = Some(clock.unix_timestamp);
 
     // 更新收益
     earnings.royalty_earnings += royalty_fee;
+    earnings.total_earnings += royalty_fee;
+    earnings.pending_amount += royalty_fee;
+    platform.total_platform_revenue = platform.total_platform_revenue
+        .checked_add(platform_fee)
+        .ok_or(TicketError::Overflow)?;
+    platform.total_transactions = platform.total_transactions
+        .checked_add(1)
+        .ok_or(TicketError::Overflow)?;
+
+    msg!(
+        "二级市场成交: 门票mint: {}, 价格: {}, 卖家所得: {}, 版税: {}, 平台手续费: {}",
+        ticket.mint,
+        price,
+        seller_amount,
+        royalty_fee,
+        platform_fee
+    );
 
     Ok(())
-} 
\ No newline at end of file
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface},
+};
 use crate::{
-    state::{MarketplaceListingAccount, TicketAccount, ListingStatus},
+    state::{MarketplaceListingAccount, TicketAccount, TicketStatus, ListingStatus},
     error::TicketError,
     constants::*,
+    utils::transfer_ticket_nft,
 };
 
 #[derive(Accounts)]
@@ -20,24 +25,81 @@ pub struct CancelTicketListing<'info> {
     #[account(
         mut,
         seeds = [TICKET_SEED, ticket.mint.as_ref()],
-        bump = ticket.bump
+        bump = ticket.bump,
+        constraint = ticket.mint == listing.ticket_mint @ TicketError::InvalidAccount
     )]
     pub ticket: Account<'info, TicketAccount>,
+
+    /// 门票NFT的mint
+    #[account(address = listing.ticket_mint @ TicketError::InvalidAccount)]
+    pub ticket_mint: InterfaceAccount<'info, Mint>,
+
+    /// 挂单托管代币账户
+    #[account(
+        mut,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 卖家的门票代币账户（接收退回的门票）
+    #[account(
+        init_if_needed,
+        payer = seller,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub seller: Signer<'info>,
+
+    /// 门票NFT所属的代币程序（SPL Token 或 Token-2022）
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<CancelTicketListing>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelTicketListing<'info>>) -> Result<()> {
     let listing = &mut ctx.accounts.listing;
-    let _ticket = &mut ctx.accounts.ticket;
+    let ticket = &mut ctx.accounts.ticket;
     let current_time = Clock::get()?.unix_timestamp;
 
+    // 门票NFT从托管账户退回卖家，并关闭托管账户
+    let listing_seeds: &[&[u8]] = &[
+        MARKETPLACE_LISTING_SEED,
+        listing.ticket_mint.as_ref(),
+        &[listing.bump],
+    ];
+    transfer_ticket_nft(
+        &ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.ticket_mint.to_account_info(),
+        ctx.accounts.seller_token_account.to_account_info(),
+        listing.to_account_info(),
+        ctx.remaining_accounts,
+        &[listing_seeds],
+    )?;
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_token_account.to_account_info(),
+            destination: ctx.accounts.seller.to_account_info(),
+            authority: listing.to_account_info(),
+        },
+        &[listing_seeds],
+    ))?;
+
     // 更新挂单状态
     listing.status = ListingStatus::Cancelled;
     listing.sold_at = Some(current_time);
 
+    // 门票恢复为有效状态
+    ticket.current_status = TicketStatus::Sold;
+
     msg!("挂单取消成功: 卖方: {}", ctx.accounts.seller.key());
 
     Ok(())
-} 
\ No newline at end of file
+}
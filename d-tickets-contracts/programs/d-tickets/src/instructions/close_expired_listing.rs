@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface},
+};
+use crate::{
+    state::{MarketplaceListingAccount, TicketAccount, TicketStatus, ListingStatus},
+    error::TicketError,
+    constants::*,
+    utils::transfer_ticket_nft,
+};
+
+/// 关闭已过期的挂单（任何人均可调用）：门票退回卖家，挂单和托管账户的租金返还卖家
+#[derive(Accounts)]
+pub struct CloseExpiredListing<'info> {
+    #[account(
+        mut,
+        seeds = [MARKETPLACE_LISTING_SEED, listing.ticket_mint.as_ref()],
+        bump = listing.bump,
+        constraint = listing.status == ListingStatus::Active @ TicketError::ListingNotFound,
+        close = seller
+    )]
+    pub listing: Account<'info, MarketplaceListingAccount>,
+
+    #[account(
+        mut,
+        seeds = [TICKET_SEED, ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.mint == listing.ticket_mint @ TicketError::InvalidAccount
+    )]
+    pub ticket: Account<'info, TicketAccount>,
+
+    /// 门票NFT的mint
+    #[account(address = listing.ticket_mint @ TicketError::InvalidAccount)]
+    pub ticket_mint: InterfaceAccount<'info, Mint>,
+
+    /// 挂单托管代币账户
+    #[account(
+        mut,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 卖家的门票代币账户（接收退回的门票，不存在时由调用者创建）
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: 卖家钱包，地址必须与挂单卖家一致，接收返还的租金
+    #[account(
+        mut,
+        address = listing.seller @ TicketError::InvalidAccount
+    )]
+    pub seller: UncheckedAccount<'info>,
+
+    /// 调用者（任何人）
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// 门票NFT所属的代币程序（SPL Token 或 Token-2022）
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CloseExpiredListing<'info>>) -> Result<()> {
+    let listing = &mut ctx.accounts.listing;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // 验证挂单已过期
+    require!(
+        listing.is_expired(current_time),
+        TicketError::ListingNotExpired
+    );
+
+    // 门票NFT从托管账户退回卖家，并关闭托管账户
+    let listing_seeds: &[&[u8]] = &[
+        MARKETPLACE_LISTING_SEED,
+        listing.ticket_mint.as_ref(),
+        &[listing.bump],
+    ];
+    transfer_ticket_nft(
+        &ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.ticket_mint.to_account_info(),
+        ctx.accounts.seller_token_account.to_account_info(),
+        listing.to_account_info(),
+        ctx.remaining_accounts,
+        &[listing_seeds],
+    )?;
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_token_account.to_account_info(),
+            destination: ctx.accounts.seller.to_account_info(),
+            authority: listing.to_account_info(),
+        },
+        &[listing_seeds],
+    ))?;
+
+    // 更新挂单状态（挂单账户在指令结束时关闭，租金退还卖家），门票恢复为有效状态
+    listing.status = ListingStatus::Expired;
+    ctx.accounts.ticket.current_status = TicketStatus::Sold;
+
+    msg!(
+        "过期挂单关闭成功: 门票mint: {}, 卖方: {}",
+        listing.ticket_mint,
+        listing.seller
+    );
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::{
+    state::{TicketAccount, TicketStatus, TicketTransferRecord, PlatformAccount},
+    error::TicketError,
+    constants::*,
+};
+
+/// 转账钩子回调：Token-2022 门票在二级市场之外转账后，由转账钩子程序调用
+#[derive(Accounts)]
+pub struct CompleteTicketTransfer<'info> {
+    /// 转账钩子程序的权限PDA，保证本指令只能由转账钩子调用
+    #[account(
+        seeds = [HOOK_AUTHORITY_SEED],
+        bump,
+        seeds::program = TRANSFER_HOOK_PROGRAM_ID
+    )]
+    pub hook_authority: Signer<'info>,
+
+    /// 门票NFT的mint
+    pub ticket_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 转出方代币账户
+    #[account(token::mint = ticket_mint)]
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 转入方代币账户
+    #[account(token::mint = ticket_mint)]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [TICKET_SEED, ticket_mint.key().as_ref()],
+        bump = ticket.bump
+    )]
+    pub ticket: Box<Account<'info, TicketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [TRANSFER_RECORD_SEED, ticket_mint.key().as_ref()],
+        bump,
+        constraint = transfer_record.ticket == ticket.key() @ TicketError::TransferNotApproved
+    )]
+    pub transfer_record: Box<Account<'info, TicketTransferRecord>>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Box<Account<'info, PlatformAccount>>,
+}
+
+pub fn handler(ctx: Context<CompleteTicketTransfer>) -> Result<()> {
+    let ticket = &mut ctx.accounts.ticket;
+    let transfer_record = &mut ctx.accounts.transfer_record;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // 验证门票状态
+    require!(
+        ticket.transferable,
+        TicketError::TicketNotTransferable
+    );
+    require!(
+        ticket.current_status == TicketStatus::Sold,
+        TicketError::InvalidTicketStatus
+    );
+
+    // 验证转账与持有者授权的转让一致（钩子在代币到账后调用）
+    require!(
+        transfer_record.transferred_at == 0,
+        TicketError::TransferNotApproved
+    );
+    require!(
+        transfer_record.from == ticket.current_owner
+            && transfer_record.from == ctx.accounts.source_token_account.owner,
+        TicketError::TransferNotApproved
+    );
+    require!(
+        transfer_record.to == ctx.accounts.destination_token_account.owner
+            && ctx.accounts.destination_token_account.amount == 1,
+        TicketError::TransferNotApproved
+    );
+
+    // 更新门票所有者
+    ticket.current_owner = transfer_record.to;
+    ticket.transfer_count += 1;
+    ticket.last_transfer_at = Some(current_time);
+
+    // 标记转让已完成
+    transfer_record.transferred_at = current_time;
+
+    msg!(
+        "门票转让完成: 门票mint: {}, 新持有者: {}",
+        ticket.mint,
+        transfer_record.to
+    );
+
+    Ok(())
+}
@@ -1,36 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::{VenueAccount, SeatAccount, SeatStatus, SeatStatusMap, TicketTypeAccount, SeatStatusUpdate, SeatInfo};
+use crate::state::{VenueAccount, SeatAccount, SeatStatus, SeatStatusMap, TicketTypeAccount, SeatStatusUpdate, SeatAccountUpdate, SeatInfo, SeatStatusPage, SeatLayoutAccount, SeatRow, SeatData, SeatPriceTierAccount, SeatPriceTier, migrate_bitmap};
 use crate::error::TicketError;
 use crate::constants::*;
-
-/// 座位售出事件
-#[event]
-pub struct SeatSoldEvent {
-    /// 活动PDA
-    pub event_pda: Pubkey,
-    /// 活动名称
-    pub event_name: String,
-    /// 票种PDA
-    pub ticket_type_pda: Pubkey,
-    /// 票种名称
-    pub ticket_type_name: String,
-    /// 区域ID
-    pub area_id: String,
-    /// 座位索引
-    pub seat_index: u32,
-    /// 座位信息
-    pub seat_row: String,
-    /// 座位号
-    pub seat_number: String,
-    /// 购买者地址
-    pub buyer: Pubkey,
-    /// 票价
-    pub ticket_price: u64,
-    /// 购买时间戳
-    pub purchased_at: i64,
-    /// 座位状态映射PDA
-    pub seat_status_map_pda: Pubkey,
-}
+use crate::utils::{close_program_account, create_pda_account};
 
 #[derive(Accounts)]
 #[instruction(venue_name: String, ticket_type_name: String, seat_number: String)]
@@ -71,9 +43,57 @@ pub struct ConfigureSeat<'info> {
 
     /// 系统程序
     pub system_program: Program<'info, System>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, crate::state::PlatformAccount>,
 }
 
 
+/// 批量配置座位（座位账户通过 remaining_accounts 传入，按 seats 的顺序一一对应）
+#[derive(Accounts)]
+#[instruction(venue_name: String, ticket_type_name: String)]
+pub struct ConfigureSeatsBatch<'info> {
+    /// 场馆创建者（主办方），预付所有座位账户的租金
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// 场馆账户（PDA）
+    #[account(
+        seeds = [b"venue", creator.key().as_ref(), venue_name.as_bytes()],
+        bump = venue_account.bump,
+        has_one = creator
+    )]
+    pub venue_account: Account<'info, VenueAccount>,
+
+    /// 票种账户（PDA）
+    #[account(
+        seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Account<'info, TicketTypeAccount>,
+
+    /// 关联的活动账户
+    #[account(
+        constraint = event.organizer == creator.key() @ TicketError::NotEventOrganizer
+    )]
+    pub event: Account<'info, crate::state::EventAccount>,
+
+    /// 系统程序
+    pub system_program: Program<'info, System>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, crate::state::PlatformAccount>,
+}
 
 #[derive(Accounts)]
 #[instruction(venue_name: String, ticket_type_name: String, seat_number: String)]
@@ -108,6 +128,92 @@ pub struct UpdateSeatStatus<'info> {
         bump = seat_account.bump
     )]
     pub seat_account: Account<'info, SeatAccount>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, crate::state::PlatformAccount>,
+}
+
+/// 将座位账户关联到座位状态映射中的座位索引
+#[derive(Accounts)]
+#[instruction(venue_name: String, ticket_type_name: String, seat_number: String)]
+pub struct LinkSeatAccount<'info> {
+    /// 场馆创建者（主办方）
+    pub creator: Signer<'info>,
+
+    /// 场馆账户（PDA）
+    #[account(
+        seeds = [b"venue", creator.key().as_ref(), venue_name.as_bytes()],
+        bump = venue_account.bump,
+        has_one = creator
+    )]
+    pub venue_account: Account<'info, VenueAccount>,
+
+    /// 活动账户
+    #[account(
+        constraint = event.organizer == creator.key() @ TicketError::NotEventOrganizer
+    )]
+    pub event: Account<'info, crate::state::EventAccount>,
+
+    /// 票种账户（PDA）
+    #[account(
+        seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Account<'info, TicketTypeAccount>,
+
+    /// 座位账户（PDA）
+    #[account(
+        mut,
+        seeds = [b"seat", venue_account.key().as_ref(), ticket_type.key().as_ref(), seat_number.as_bytes()],
+        bump = seat_account.bump
+    )]
+    pub seat_account: Account<'info, SeatAccount>,
+
+    /// 座位所在区域的座位状态映射账户
+    #[account(
+        seeds = [b"seat_status_map", event.key().as_ref(), ticket_type.key().as_ref(), seat_account.area_id.as_bytes()],
+        bump = seat_status_map.bump
+    )]
+    pub seat_status_map: Account<'info, SeatStatusMap>,
+
+    /// 座位所在的座位状态分页账户
+    #[account(
+        constraint = seat_status_page.load()?.seat_status_map == seat_status_map.key() @ TicketError::InvalidAccount
+    )]
+    pub seat_status_page: AccountLoader<'info, SeatStatusPage>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, crate::state::PlatformAccount>,
+}
+
+/// 校验并修复座位账户与座位状态映射的一致性（任何人均可调用）
+#[derive(Accounts)]
+pub struct SyncSeatAccount<'info> {
+    /// 已关联座位状态映射的座位账户
+    #[account(
+        mut,
+        constraint = seat_account.seat_status_map == Some(seat_status_map.key()) @ TicketError::InvalidAccount
+    )]
+    pub seat_account: Account<'info, SeatAccount>,
+
+    /// 座位状态映射账户
+    pub seat_status_map: Account<'info, SeatStatusMap>,
+
+    /// 座位所在的座位状态分页账户
+    #[account(
+        constraint = seat_status_page.load()?.seat_status_map == seat_status_map.key() @ TicketError::InvalidAccount
+    )]
+    pub seat_status_page: AccountLoader<'info, SeatStatusPage>,
 }
 
 /// 创建或更新座位状态映射账户
@@ -144,23 +250,82 @@ pub struct CreateOrUpdateSeatStatusMap<'info> {
 
     /// 系统程序
     pub system_program: Program<'info, System>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, crate::state::PlatformAccount>,
 }
 
-/// 批量更新座位状态（新方案）
+/// 创建座位状态分页账户（每页16000个座位，创建座位状态映射后按页创建，已创建的分页不能重复初始化）
 #[derive(Accounts)]
-#[instruction(ticket_type_name: String, area_id: String)]
-pub struct BatchUpdateSeatStatus<'info> {
-    /// 授权用户（可以是创建者或购票者）
+#[instruction(ticket_type_name: String, area_id: String, page_index: u16)]
+pub struct CreateSeatStatusPage<'info> {
+    /// 活动创建者
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub creator: Signer<'info>,
 
     /// 活动账户
+    #[account(
+        constraint = event.organizer == creator.key() @ TicketError::NotEventOrganizer
+    )]
+    pub event: Account<'info, crate::state::EventAccount>,
+
+    /// 票种账户
+    #[account(
+        seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Account<'info, TicketTypeAccount>,
+
+    /// 座位状态映射账户
+    #[account(
+        seeds = [b"seat_status_map", event.key().as_ref(), ticket_type.key().as_ref(), area_id.as_bytes()],
+        bump = seat_status_map.bump
+    )]
+    pub seat_status_map: Account<'info, SeatStatusMap>,
+
+    /// 座位状态分页账户（PDA）
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + SeatStatusPage::SPACE,
+        seeds = [SEAT_STATUS_PAGE_SEED, seat_status_map.key().as_ref(), &page_index.to_le_bytes()],
+        bump
+    )]
+    pub seat_status_page: AccountLoader<'info, SeatStatusPage>,
+
+    /// 系统程序
+    pub system_program: Program<'info, System>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, crate::state::PlatformAccount>,
+}
+
+/// 登记或追加座位布局（座位索引到排号和座位号的对应关系）
+#[derive(Accounts)]
+#[instruction(ticket_type_name: String, area_id: String)]
+pub struct UpdateSeatLayout<'info> {
+    /// 活动创建者
     #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// 活动账户
+    #[account(
+        constraint = event.organizer == creator.key() @ TicketError::NotEventOrganizer
+    )]
     pub event: Account<'info, crate::state::EventAccount>,
 
     /// 票种账户
     #[account(
-        mut,
         seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
         bump = ticket_type.bump
     )]
@@ -174,23 +339,207 @@ pub struct BatchUpdateSeatStatus<'info> {
     )]
     pub seat_status_map: Account<'info, SeatStatusMap>,
 
-    /// 主办方收益账户
+    /// 座位布局账户（PDA）
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + SeatLayoutAccount::INIT_SPACE,
+        seeds = [SEAT_LAYOUT_SEED, seat_status_map.key().as_ref()],
+        bump
+    )]
+    pub seat_layout: Account<'info, SeatLayoutAccount>,
+
+    /// 系统程序
+    pub system_program: Program<'info, System>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, crate::state::PlatformAccount>,
+}
+
+/// 设置座位价格档位
+#[derive(Accounts)]
+#[instruction(ticket_type_name: String, area_id: String)]
+pub struct SetSeatPriceTiers<'info> {
+    /// 活动创建者
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// 活动账户
+    #[account(
+        constraint = event.organizer == creator.key() @ TicketError::NotEventOrganizer
+    )]
+    pub event: Account<'info, crate::state::EventAccount>,
+
+    /// 票种账户
+    #[account(
+        seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Account<'info, TicketTypeAccount>,
+
+    /// 座位状态映射账户
     #[account(
         mut,
-        seeds = [EARNINGS_SEED, event.key().as_ref()],
-        bump = earnings.bump
+        seeds = [b"seat_status_map", event.key().as_ref(), ticket_type.key().as_ref(), area_id.as_bytes()],
+        bump = seat_status_map.bump
     )]
-    pub earnings: Account<'info, crate::state::OrganizerEarnings>,
+    pub seat_status_map: Account<'info, SeatStatusMap>,
 
-    /// 平台账户
+    /// 座位价格档位账户（PDA）
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + SeatPriceTierAccount::INIT_SPACE,
+        seeds = [SEAT_PRICE_TIERS_SEED, seat_status_map.key().as_ref()],
+        bump
+    )]
+    pub seat_price_tiers: Account<'info, SeatPriceTierAccount>,
+
+    /// 系统程序
+    pub system_program: Program<'info, System>,
+
+    /// 平台账户（平台暂停时拒绝执行）
     #[account(
         seeds = [PLATFORM_SEED],
-        bump = platform.bump
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
     )]
     pub platform: Account<'info, crate::state::PlatformAccount>,
+}
+
+/// 迁移座位状态映射至分页位图（旧版内联位图）
+#[derive(Accounts)]
+#[instruction(ticket_type_name: String, area_id: String)]
+pub struct MigrateSeatStatusMap<'info> {
+    /// 活动创建者（支付分页账户租金，接收缩容后多余的租金）
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// 活动账户
+    #[account(
+        constraint = event.organizer == creator.key() @ TicketError::NotEventOrganizer
+    )]
+    pub event: Account<'info, crate::state::EventAccount>,
+
+    /// 票种账户
+    #[account(
+        seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Account<'info, TicketTypeAccount>,
+
+    /// CHECK: 旧版座位状态映射账户，按旧版布局读取，迁移后改写为当前布局并缩容
+    #[account(
+        mut,
+        owner = crate::ID @ TicketError::InvalidAccount,
+        seeds = [b"seat_status_map", event.key().as_ref(), ticket_type.key().as_ref(), area_id.as_bytes()],
+        bump
+    )]
+    pub seat_status_map: UncheckedAccount<'info>,
+
+    /// 第0页座位状态分页账户（接收旧版内联位图）
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + SeatStatusPage::SPACE,
+        seeds = [SEAT_STATUS_PAGE_SEED, seat_status_map.key().as_ref(), &0u16.to_le_bytes()],
+        bump
+    )]
+    pub seat_status_page: AccountLoader<'info, SeatStatusPage>,
 
     /// 系统程序
     pub system_program: Program<'info, System>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, crate::state::PlatformAccount>,
+}
+
+/// 批量更新座位状态（新方案）
+///
+/// 仅限主办方调整座位的管理状态（可用、锁定、不可用等），不能售出座位或改动已售出的座位；
+/// 售座统一通过 purchase_seat 在同一指令中完成付款并铸造门票NFT
+#[derive(Accounts)]
+#[instruction(ticket_type_name: String, area_id: String)]
+pub struct BatchUpdateSeatStatus<'info> {
+    /// 活动主办方
+    pub authority: Signer<'info>,
+
+    /// 活动账户
+    #[account(
+        mut,
+        seeds = [EVENT_SEED, event.organizer.as_ref(), event.event_name.as_bytes()],
+        bump = event.bump,
+        constraint = event.organizer == authority.key() @ TicketError::NotEventOrganizer
+    )]
+    pub event: Box<Account<'info, crate::state::EventAccount>>,
+
+    /// 票种账户
+    #[account(
+        seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Box<Account<'info, TicketTypeAccount>>,
+
+    /// 座位状态映射账户
+    #[account(
+        mut,
+        seeds = [b"seat_status_map", event.key().as_ref(), ticket_type.key().as_ref(), area_id.as_bytes()],
+        bump = seat_status_map.bump
+    )]
+    pub seat_status_map: Box<Account<'info, SeatStatusMap>>,
+
+    /// 座位所在的座位状态分页账户（同一次操作的座位需位于同一分页）
+    #[account(
+        mut,
+        constraint = seat_status_page.load()?.seat_status_map == seat_status_map.key() @ TicketError::InvalidAccount
+    )]
+    pub seat_status_page: AccountLoader<'info, SeatStatusPage>,
+
+    /// 平台账户
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Box<Account<'info, crate::state::PlatformAccount>>,
+}
+
+/// 查询座位状态（只读，平台暂停时仍可使用）
+#[derive(Accounts)]
+#[instruction(ticket_type_name: String, area_id: String)]
+pub struct GetSeatStatusBatch<'info> {
+    /// 活动账户
+    pub event: Account<'info, crate::state::EventAccount>,
+
+    /// 票种账户
+    #[account(
+        seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Account<'info, TicketTypeAccount>,
+
+    /// 座位状态映射账户
+    #[account(
+        seeds = [b"seat_status_map", event.key().as_ref(), ticket_type.key().as_ref(), area_id.as_bytes()],
+        bump = seat_status_map.bump
+    )]
+    pub seat_status_map: Account<'info, SeatStatusMap>,
+
+    /// 座位所在的座位状态分页账户（同一次操作的座位需位于同一分页）
+    #[account(
+        constraint = seat_status_page.load()?.seat_status_map == seat_status_map.key() @ TicketError::InvalidAccount
+    )]
+    pub seat_status_page: AccountLoader<'info, SeatStatusPage>,
 }
 
 /// 删除座位状态映射账户
@@ -223,6 +572,30 @@ pub struct DeleteSeatStatusMap<'info> {
         bump = seat_status_map.bump
     )]
     pub seat_status_map: Account<'info, SeatStatusMap>,
+
+    /// CHECK: 座位布局账户（PDA），已创建时随映射一并关闭
+    #[account(
+        mut,
+        seeds = [SEAT_LAYOUT_SEED, seat_status_map.key().as_ref()],
+        bump
+    )]
+    pub seat_layout: UncheckedAccount<'info>,
+
+    /// CHECK: 座位价格档位账户（PDA），已创建时随映射一并关闭
+    #[account(
+        mut,
+        seeds = [SEAT_PRICE_TIERS_SEED, seat_status_map.key().as_ref()],
+        bump
+    )]
+    pub seat_price_tiers: UncheckedAccount<'info>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, crate::state::PlatformAccount>,
 }
 
 
@@ -259,6 +632,8 @@ pub fn configure_seat(
     seat_account.seat_status = SeatStatus::Available;
     seat_account.ticket_nft = None;
     seat_account.ticket_type_id = Some(ticket_type.ticket_type_id);
+    seat_account.seat_status_map = None;
+    seat_account.seat_index = None;
     seat_account.created_at = clock.unix_timestamp;
     seat_account.updated_at = clock.unix_timestamp;
     seat_account.bump = ctx.bumps.seat_account;
@@ -268,6 +643,143 @@ pub fn configure_seat(
     Ok(())
 }
 
+/// 批量配置座位：每个座位账户（PDA）按 seats 的顺序通过 remaining_accounts 传入，由创建者支付租金
+pub fn configure_seats_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, ConfigureSeatsBatch<'info>>,
+    _venue_name: String,
+    ticket_type_name: String,
+    seats: Vec<SeatData>,
+) -> Result<()> {
+    let venue_key = ctx.accounts.venue_account.key();
+    let ticket_type = &ctx.accounts.ticket_type;
+    let ticket_type_key = ticket_type.key();
+    let event = &ctx.accounts.event;
+    let creator = ctx.accounts.creator.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let clock = Clock::get()?;
+
+    // 验证座位数量及对应的账户数量
+    require!(!seats.is_empty(), TicketError::RequiredFieldEmpty);
+    require!(
+        seats.len() <= MAX_SEATS_PER_CONFIGURE_BATCH,
+        TicketError::TooManySeats
+    );
+    require!(
+        ctx.remaining_accounts.len() == seats.len(),
+        TicketError::InvalidAccount
+    );
+
+    for (seat, seat_account) in seats.into_iter().zip(ctx.remaining_accounts.iter()) {
+        // 验证输入参数
+        require!(seat.seat_number.len() <= 20, TicketError::SeatNumberTooLong);
+        require!(seat.area_id.len() <= 20, TicketError::AreaIdTooLong);
+        require!(seat.row_number.len() <= 10, TicketError::RowNumberTooLong);
+        require!(seat.seat_number_in_row.len() <= 10, TicketError::SeatNumberInRowTooLong);
+
+        // 验证区域已关联到该活动的票种（映射格式：票种名-区域ID）
+        require!(
+            event.has_ticket_area_mapping(&ticket_type_name, &seat.area_id),
+            TicketError::AreaNotMappedToEvent
+        );
+
+        // 座位账户（PDA，与单个配置使用相同的种子）
+        let (seat_key, seat_bump) = Pubkey::find_program_address(
+            &[b"seat", venue_key.as_ref(), ticket_type_key.as_ref(), seat.seat_number.as_bytes()],
+            ctx.program_id,
+        );
+        require_keys_eq!(seat_account.key(), seat_key, TicketError::InvalidAccount);
+        create_pda_account(
+            creator.clone(),
+            seat_account.clone(),
+            system_program.clone(),
+            8 + SeatAccount::INIT_SPACE,
+            ctx.program_id,
+            &[b"seat", venue_key.as_ref(), ticket_type_key.as_ref(), seat.seat_number.as_bytes(), &[seat_bump]],
+        )?;
+
+        let seat_data = SeatAccount {
+            venue: venue_key,
+            ticket_type_key,
+            event: Some(event.key()),
+            seat_number: seat.seat_number,
+            area_id: seat.area_id,
+            row_number: seat.row_number,
+            seat_number_in_row: seat.seat_number_in_row,
+            seat_status: SeatStatus::Available,
+            ticket_nft: None,
+            ticket_type_id: Some(ticket_type.ticket_type_id),
+            created_at: clock.unix_timestamp,
+            updated_at: clock.unix_timestamp,
+            bump: seat_bump,
+            seat_status_map: None,
+            seat_index: None,
+        };
+        seat_data.try_serialize(&mut &mut seat_account.try_borrow_mut_data()?[..])?;
+    }
+
+    msg!(
+        "批量座位配置成功: 票种={}, 座位数={}",
+        ticket_type_name,
+        ctx.remaining_accounts.len()
+    );
+
+    Ok(())
+}
+
+/// 将座位账户关联到座位状态映射中的座位索引，并按位图同步座位状态
+pub fn link_seat_account(
+    ctx: Context<LinkSeatAccount>,
+    _venue_name: String,
+    _ticket_type_name: String,
+    _seat_number: String,
+    seat_index: u32,
+) -> Result<()> {
+    let seat_account = &mut ctx.accounts.seat_account;
+    let seat_status_map = &ctx.accounts.seat_status_map;
+    let seat_status_page = ctx.accounts.seat_status_page.load()?;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // 读取位图中的座位状态（同时验证座位索引和分页）
+    let status = seat_status_map.get_seat_status(&seat_status_page, seat_index)?;
+
+    seat_account.event = Some(seat_status_map.event);
+    seat_account.seat_status_map = Some(seat_status_map.key());
+    seat_account.seat_index = Some(seat_index);
+    seat_account.sync_status(status, current_time);
+    seat_account.updated_at = current_time;
+
+    msg!(
+        "座位关联成功: {} -> 座位索引 {}, 状态 {:?}",
+        seat_account.seat_number,
+        seat_index,
+        seat_account.seat_status
+    );
+
+    Ok(())
+}
+
+/// 校验座位账户与位图中的座位状态是否一致，不一致时以位图为准修复
+pub fn sync_seat_account(ctx: Context<SyncSeatAccount>) -> Result<()> {
+    let seat_account = &mut ctx.accounts.seat_account;
+    let seat_status_page = ctx.accounts.seat_status_page.load()?;
+    let seat_index = seat_account.seat_index.ok_or(TicketError::InvalidAccount)?;
+
+    let status = ctx.accounts.seat_status_map.get_seat_status(&seat_status_page, seat_index)?;
+    let previous_status = seat_account.seat_status.clone();
+    if seat_account.sync_status(status, Clock::get()?.unix_timestamp) {
+        msg!(
+            "座位状态已修复: {} {:?} -> {:?}",
+            seat_account.seat_number,
+            previous_status,
+            seat_account.seat_status
+        );
+    } else {
+        msg!("座位状态一致: {} {:?}", seat_account.seat_number, seat_account.seat_status);
+    }
+
+    Ok(())
+}
+
 /// 创建或更新座位状态映射（支持添加和修改两种情况）
 pub fn create_seat_status_map(
     ctx: Context<CreateOrUpdateSeatStatusMap>,
@@ -325,7 +837,7 @@ pub fn create_seat_status_map(
             TicketError::CannotModifyWithSoldSeats
         );
         
-        // 如果座位数量发生变化，需要重新初始化位图
+        // 如果座位数量发生变化，需要重新初始化位图（之后重新创建分页账户）
         if seat_status_map.total_seats != total_seats {
             seat_status_map.initialize_bitmap(total_seats)?;
         }
@@ -354,8 +866,11 @@ pub fn create_seat_status_map(
 }
 
 /// 删除座位状态映射
-pub fn delete_seat_status_map(
-    ctx: Context<DeleteSeatStatusMap>,
+///
+/// 映射的全部座位状态分页账户按分页序号依次通过 remaining_accounts 传入（未创建的分页也需传入对应地址），
+/// 与座位布局、价格档位账户一并关闭并返还租金
+pub fn delete_seat_status_map<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DeleteSeatStatusMap<'info>>,
     ticket_type_name: String,
     area_id: String,
 ) -> Result<()> {
@@ -369,6 +884,27 @@ pub fn delete_seat_status_map(
         TicketError::CannotDeleteWithSoldSeats
     );
 
+    // 关闭全部座位状态分页账户
+    let page_count = seat_status_map.total_seats.div_ceil(SEATS_PER_BITMAP_PAGE) as usize;
+    require!(
+        ctx.remaining_accounts.len() == page_count,
+        TicketError::InvalidAccount
+    );
+    let creator = ctx.accounts.creator.to_account_info();
+    let seat_status_map_key = seat_status_map.key();
+    for (page_index, seat_status_page) in ctx.remaining_accounts.iter().enumerate() {
+        let (page_key, _) = Pubkey::find_program_address(
+            &[SEAT_STATUS_PAGE_SEED, seat_status_map_key.as_ref(), &(page_index as u16).to_le_bytes()],
+            ctx.program_id,
+        );
+        require_keys_eq!(seat_status_page.key(), page_key, TicketError::InvalidAccount);
+        close_program_account(seat_status_page, &creator)?;
+    }
+
+    // 关闭座位布局和价格档位账户
+    close_program_account(&ctx.accounts.seat_layout.to_account_info(), &creator)?;
+    close_program_account(&ctx.accounts.seat_price_tiers.to_account_info(), &creator)?;
+
     // 从活动的票种-区域映射列表中删除对应映射
     event_account.remove_ticket_area_mapping(&ticket_type_name, &area_id)?;
 
@@ -384,126 +920,285 @@ pub fn delete_seat_status_map(
     Ok(())
 }
 
+/// 创建座位状态分页账户
+pub fn create_seat_status_page(
+    ctx: Context<CreateSeatStatusPage>,
+    ticket_type_name: String,
+    area_id: String,
+    page_index: u16,
+) -> Result<()> {
+    let seat_status_map = &ctx.accounts.seat_status_map;
+    let clock = Clock::get()?;
+
+    // 验证分页序号在座位总数范围内
+    require!(
+        seat_status_map.is_valid_page(page_index),
+        TicketError::InvalidSeatIndex
+    );
+
+    let mut seat_status_page = ctx.accounts.seat_status_page.load_init()?;
+
+    // 初始化分页位图（所有座位默认为可用状态）
+    let start_index = SeatStatusMap::page_start_index(page_index);
+    let seat_count = (seat_status_map.total_seats - start_index).min(SEATS_PER_BITMAP_PAGE);
+    seat_status_page.seat_status_map = seat_status_map.key();
+    seat_status_page.page_index = page_index;
+    seat_status_page.start_index = start_index;
+    seat_status_page.seat_count = seat_count;
+    seat_status_page.created_at = clock.unix_timestamp;
+    seat_status_page.updated_at = clock.unix_timestamp;
+    seat_status_page.bump = ctx.bumps.seat_status_page;
+
+    msg!(
+        "座位状态分页创建成功: 票种={}, 区域={}, 分页={}, 座位范围={}..{}",
+        ticket_type_name,
+        area_id,
+        page_index,
+        start_index,
+        start_index + seat_count
+    );
+
+    Ok(())
+}
+
+/// 登记或追加座位布局（单笔交易容量有限，大型区域可分多次追加）
+pub fn update_seat_layout(
+    ctx: Context<UpdateSeatLayout>,
+    ticket_type_name: String,
+    area_id: String,
+    rows: Vec<SeatRow>,
+    reset: bool,
+) -> Result<()> {
+    let seat_status_map = &mut ctx.accounts.seat_status_map;
+    let seat_layout = &mut ctx.accounts.seat_layout;
+    let clock = Clock::get()?;
+
+    // 已售出座位的排号和座位号已写入门票，不能再修改布局
+    require!(
+        seat_status_map.sold_seats == 0,
+        TicketError::CannotModifyWithSoldSeats
+    );
+    require!(!rows.is_empty(), TicketError::RequiredFieldEmpty);
+
+    if seat_layout.seat_status_map == Pubkey::default() {
+        seat_layout.seat_status_map = seat_status_map.key();
+        seat_layout.created_at = clock.unix_timestamp;
+        seat_layout.bump = ctx.bumps.seat_layout;
+    }
+    if reset {
+        seat_layout.rows.clear();
+    }
+
+    // 验证追加后的完整布局
+    seat_layout.rows.extend(rows);
+    require!(seat_layout.rows.len() <= 200, TicketError::InvalidSeatLayout);
+    SeatLayoutAccount::validate_rows(&seat_layout.rows, seat_status_map.total_seats)?;
+    seat_layout.updated_at = clock.unix_timestamp;
+
+    seat_status_map.has_seat_layout = true;
+    seat_status_map.updated_at = clock.unix_timestamp;
+
+    msg!(
+        "座位布局更新成功: 票种={}, 区域={}, 排数={}",
+        ticket_type_name,
+        area_id,
+        seat_layout.rows.len()
+    );
+
+    Ok(())
+}
+
+/// 设置座位价格档位（整体替换，传入空列表即取消档位定价）
+///
+/// 已售出门票按购买时记录的价格退款，修改档位只影响之后的售座
+pub fn set_seat_price_tiers(
+    ctx: Context<SetSeatPriceTiers>,
+    ticket_type_name: String,
+    area_id: String,
+    tiers: Vec<SeatPriceTier>,
+) -> Result<()> {
+    let seat_status_map = &mut ctx.accounts.seat_status_map;
+    let seat_price_tiers = &mut ctx.accounts.seat_price_tiers;
+    let clock = Clock::get()?;
+
+    // 验证价格档位
+    SeatPriceTierAccount::validate_tiers(&tiers, seat_status_map.total_seats)?;
+
+    if seat_price_tiers.seat_status_map == Pubkey::default() {
+        seat_price_tiers.seat_status_map = seat_status_map.key();
+        seat_price_tiers.created_at = clock.unix_timestamp;
+        seat_price_tiers.bump = ctx.bumps.seat_price_tiers;
+    }
+    seat_price_tiers.tiers = tiers;
+    seat_price_tiers.updated_at = clock.unix_timestamp;
+
+    seat_status_map.has_price_tiers = !seat_price_tiers.tiers.is_empty();
+    seat_status_map.updated_at = clock.unix_timestamp;
+
+    msg!(
+        "座位价格档位设置成功: 票种={}, 区域={}, 档位数={}",
+        ticket_type_name,
+        area_id,
+        seat_price_tiers.tiers.len()
+    );
+
+    Ok(())
+}
+
+/// 旧版座位状态映射布局（内联每座位2位位图），仅在迁移时读取
+#[derive(AnchorDeserialize)]
+struct LegacySeatStatusMap {
+    event: Pubkey,
+    ticket_type: Pubkey,
+    seat_layout_hash: String,
+    seat_index_map_hash: String,
+    total_seats: u32,
+    sold_seats: u32,
+    seat_status_bitmap: Vec<u8>,
+    created_at: i64,
+    _updated_at: i64,
+    bump: u8,
+}
+
+/// 迁移座位状态映射：旧版内联位图写入第0页分页账户，映射账户改写为当前布局
+pub fn migrate_seat_status_map(
+    ctx: Context<MigrateSeatStatusMap>,
+    ticket_type_name: String,
+    area_id: String,
+) -> Result<()> {
+    let seat_status_map_info = ctx.accounts.seat_status_map.to_account_info();
+
+    // 按旧版布局读取（已迁移的账户使用当前布局的discriminator）
+    let legacy = {
+        let data = seat_status_map_info.try_borrow_data()?;
+        require!(data.len() >= 8, TicketError::InvalidAccount);
+        require!(
+            data[..8] != *SeatStatusMap::DISCRIMINATOR,
+            TicketError::SeatStatusMapAlreadyMigrated
+        );
+        require!(
+            data[..8] == SeatStatusMap::LEGACY_DISCRIMINATOR,
+            TicketError::InvalidAccount
+        );
+        LegacySeatStatusMap::deserialize(&mut &data[8..])?
+    };
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let mut seat_status_page = ctx.accounts.seat_status_page.load_init()?;
+    seat_status_page.seat_status_map = seat_status_map_info.key();
+    seat_status_page.page_index = 0;
+    seat_status_page.start_index = 0;
+    seat_status_page.seat_count = legacy.total_seats.min(SEATS_PER_BITMAP_PAGE);
+    seat_status_page.created_at = current_time;
+    seat_status_page.updated_at = current_time;
+    seat_status_page.bump = ctx.bumps.seat_status_page;
+    migrate_bitmap(
+        &legacy.seat_status_bitmap,
+        legacy.total_seats,
+        &mut seat_status_page.seat_status_bitmap,
+    )?;
+
+    let seat_status_map = SeatStatusMap {
+        event: legacy.event,
+        ticket_type: legacy.ticket_type,
+        seat_layout_hash: legacy.seat_layout_hash,
+        seat_index_map_hash: legacy.seat_index_map_hash,
+        total_seats: legacy.total_seats,
+        sold_seats: legacy.sold_seats,
+        created_at: legacy.created_at,
+        updated_at: current_time,
+        bump: legacy.bump,
+        has_seat_layout: false,
+        has_price_tiers: false,
+    };
+
+    // 当前布局不再包含内联位图，缩容后将多余的租金退还创建者
+    let new_space = 8 + SeatStatusMap::INIT_SPACE;
+    seat_status_map_info.realloc(new_space, false)?;
+    let excess_lamports = seat_status_map_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(new_space));
+    seat_status_map_info.sub_lamports(excess_lamports)?;
+    ctx.accounts.creator.add_lamports(excess_lamports)?;
+    {
+        let mut data = seat_status_map_info.try_borrow_mut_data()?;
+        seat_status_map.try_serialize(&mut &mut data[..])?;
+    }
+
+    msg!(
+        "座位状态映射迁移成功: 票种={}, 区域={}, 总座位数={}, 已售={}",
+        ticket_type_name,
+        area_id,
+        seat_status_map.total_seats,
+        seat_status_map.sold_seats
+    );
+
+    Ok(())
+}
+
 /// 批量更新座位状态
-pub fn batch_update_seat_status(
-    ctx: Context<BatchUpdateSeatStatus>,
+///
+/// 已关联到本映射的座位账户（详细记录）可通过 remaining_accounts 传入，在同一指令内按位图同步状态
+pub fn batch_update_seat_status<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchUpdateSeatStatus<'info>>,
     _ticket_type_name: String,
     _area_id: String,
     seat_updates: Vec<SeatStatusUpdate>,
 ) -> Result<()> {
     let seat_status_map = &mut ctx.accounts.seat_status_map;
-    let ticket_type = &mut ctx.accounts.ticket_type;
     let event = &mut ctx.accounts.event;
-    let earnings = &mut ctx.accounts.earnings;
-    let platform = &ctx.accounts.platform;
+    let mut seat_status_page = ctx.accounts.seat_status_page.load_mut()?;
     let current_time = Clock::get()?.unix_timestamp;
 
     // 限制批量更新数量
     require!(seat_updates.len() <= 50, TicketError::TooManySeats);
-    require!(seat_updates.len() > 0, TicketError::RequiredFieldEmpty);
+    require!(!seat_updates.is_empty(), TicketError::RequiredFieldEmpty);
 
     msg!("开始批量更新座位状态: {} 个座位", seat_updates.len());
 
     // 分别处理每个座位更新
     for update in seat_updates {
-        let old_status = seat_status_map.get_seat_status(update.seat_index)?;
-        
-        // 如果是从可用状态变为已售出状态，需要特殊处理
-        if old_status != SeatStatus::Sold && update.new_status == SeatStatus::Sold {
-            // 验证购票者信息和座位信息是否提供
-            let buyer = update.buyer.ok_or(TicketError::RequiredFieldEmpty)?;
-            let seat_info = update.seat_info.as_ref().ok_or(TicketError::RequiredFieldEmpty)?;
-            
-            // 验证票种库存
-            require!(
-                ticket_type.sold_count < ticket_type.total_supply,
-                TicketError::InsufficientTicketSupply
-            );
-
-            // 计算价格和费用
-            let ticket_price = ticket_type.current_price;
-            let platform_fee = ticket_price
-                .checked_mul(platform.platform_fee_bps as u64)
-                .unwrap()
-                .checked_div(10000)
-                .unwrap();
-            let organizer_amount = ticket_price.checked_sub(platform_fee).unwrap();
-
-            // 更新统计数据和座位状态
-            
-            // 更新票种和活动统计数据
-            ticket_type.sold_count += 1;
-            event.total_tickets_sold += 1;
-            event.total_revenue += ticket_price;
-            earnings.total_earnings += organizer_amount;
-            earnings.pending_amount += organizer_amount;
-
-            // 发出座位售出事件，供后台监听和处理NFT铸造
-            emit!(SeatSoldEvent {
-                event_pda: event.key(),
-                event_name: event.event_name.clone(),
-                ticket_type_pda: ticket_type.key(),
-                ticket_type_name: ticket_type.type_name.clone(),
-                area_id: seat_info.area_id.clone(),
-                seat_index: update.seat_index,
-                seat_row: seat_info.row_number.clone(),
-                seat_number: seat_info.seat_number_in_row.clone(),
-                buyer,
-                ticket_price,
-                purchased_at: current_time,
-                seat_status_map_pda: seat_status_map.key(),
-            });
-
-            msg!(
-                "座位售出 - 活动:{}, 票种:{}, 区域:{}, 座位索引:{}, 排号:{}, 座号:{}, 购买者:{}, 价格:{} lamports",
-                event.event_name,
-                ticket_type.type_name,
-                seat_info.area_id,
-                update.seat_index,
-                seat_info.row_number, 
-                seat_info.seat_number_in_row,
-                buyer,
-                ticket_price
-            );
-
-        } 
-        // else if old_status == SeatStatus::Sold && update.new_status != SeatStatus::Sold {
-        //     // 如果是从已售出状态变为其他状态（如退票），需要处理退款逻辑
-            
-        //     // 计算退款金额
-        //     let refund_amount = ticket_type.current_price;
-            
-        //     // 更新统计数据
-        //     ticket_type.sold_count = ticket_type.sold_count.saturating_sub(1);
-        //     ticket_type.refunded_count += 1;
-        //     event.total_tickets_sold = event.total_tickets_sold.saturating_sub(1);
-        //     event.total_tickets_refunded += 1;
-        //     event.total_revenue = event.total_revenue.saturating_sub(refund_amount);
-        //     earnings.total_earnings = earnings.total_earnings.saturating_sub(refund_amount);
-        //     earnings.pending_amount = earnings.pending_amount.saturating_sub(refund_amount);
-
-        //     msg!(
-        //         "座位 {} 从已售出状态变更为 {:?}: 退款金额={} lamports",
-        //         update.seat_index,
-        //         update.new_status,
-        //         refund_amount
-        //     );
-        // }
+        let old_status = seat_status_map.get_seat_status(&seat_status_page, update.seat_index)?;
+
+        // 不能售出座位或改动已售出、已核销的座位（售座走 purchase_seat，退票走 refund_ticket，核销走 redeem_ticket），
+        // 临时锁定只能通过 hold_seats 创建
+        require!(
+            !old_status.is_sold()
+                && !update.new_status.is_sold()
+                && update.new_status != SeatStatus::TempLocked,
+            TicketError::InvalidSeatStatus
+        );
 
         // 更新座位状态
-        seat_status_map.update_seat_status(update.seat_index, update.new_status.clone())?;
+        seat_status_map.update_seat_status(
+            &mut seat_status_page,
+            update.seat_index,
+            update.new_status.clone(),
+        )?;
         msg!("座位索引 {} 状态更新为 {:?}", update.seat_index, update.new_status);
     }
 
+    // 同步传入的座位详细记录（须位于同一分页）
+    for seat_account_info in ctx.remaining_accounts.iter() {
+        let mut seat_account = Account::<SeatAccount>::try_from(seat_account_info)?;
+        require!(
+            seat_account.seat_status_map == Some(seat_status_map.key()),
+            TicketError::InvalidAccount
+        );
+        let seat_index = seat_account.seat_index.ok_or(TicketError::InvalidAccount)?;
+        let status = seat_status_map.get_seat_status(&seat_status_page, seat_index)?;
+        if seat_account.sync_status(status, current_time) {
+            seat_account.exit(ctx.program_id)?;
+        }
+    }
+
     // 更新时间戳
     event.updated_at = current_time;
-    ticket_type.last_price_update = current_time;
 
     msg!(
-        "批量座位状态更新完成: 已售={}/{}, 总收入={} lamports",
+        "批量座位状态更新完成: 已售={}/{}",
         seat_status_map.sold_seats,
-        seat_status_map.total_seats,
-        event.total_revenue
+        seat_status_map.total_seats
     );
 
     Ok(())
@@ -511,18 +1206,19 @@ pub fn batch_update_seat_status(
 
 /// 查询座位状态（只读操作）
 pub fn get_seat_status_batch(
-    ctx: Context<BatchUpdateSeatStatus>,
+    ctx: Context<GetSeatStatusBatch>,
     _ticket_type_name: String,
     _area_id: String,
     seat_indices: Vec<u32>,
 ) -> Result<()> {
     let seat_status_map = &ctx.accounts.seat_status_map;
+    let seat_status_page = ctx.accounts.seat_status_page.load()?;
 
     require!(seat_indices.len() <= 100, TicketError::TooManySeats);
 
     msg!("查询座位状态:");
     for seat_index in seat_indices {
-        let status = seat_status_map.get_seat_status(seat_index)?;
+        let status = seat_status_map.get_seat_status(&seat_status_page, seat_index)?;
         msg!("座位索引 {}: {:?}", seat_index, status);
     }
 
@@ -534,32 +1230,35 @@ pub fn update_seat_status(
     _venue_name: String,
     _ticket_type_name: String,
     _seat_number: String,
-    new_status: SeatStatus,
-    event_key: Option<Pubkey>,
-    ticket_nft: Option<Pubkey>,
-    ticket_type_id: Option<u8>,
+    update: SeatAccountUpdate,
 ) -> Result<()> {
     let seat_account = &mut ctx.accounts.seat_account;
     let clock = Clock::get()?;
 
+    // 已关联座位状态映射的座位以位图为准，不能单独修改
+    require!(
+        seat_account.seat_status_map.is_none(),
+        TicketError::SeatManagedByStatusMap
+    );
+
     // 更新座位状态
-    seat_account.seat_status = new_status.clone();
+    seat_account.seat_status = update.new_status.clone();
     
-    if let Some(event) = event_key {
+    if let Some(event) = update.event_key {
         seat_account.event = Some(event);
     }
     
-    if let Some(nft) = ticket_nft {
+    if let Some(nft) = update.ticket_nft {
         seat_account.ticket_nft = Some(nft);
     }
     
-    if let Some(type_id) = ticket_type_id {
+    if let Some(type_id) = update.ticket_type_id {
         seat_account.ticket_type_id = Some(type_id);
     }
 
     seat_account.updated_at = clock.unix_timestamp;
 
-    msg!("座位状态更新成功: {} -> {:?}", seat_account.seat_number, new_status);
+    msg!("座位状态更新成功: {} -> {:?}", seat_account.seat_number, update.new_status);
     
     Ok(())
 }
@@ -16,7 +16,7 @@ pub struct CreateEvent<'info> {
     #[account(
         init,
         payer = organizer,
-        space = OrganizerEarnings::INIT_SPACE,
+        space = 8 + OrganizerEarnings::INIT_SPACE,
         seeds = [EARNINGS_SEED, event.key().as_ref()],
         bump
     )]
@@ -24,7 +24,8 @@ pub struct CreateEvent<'info> {
     
     #[account(
         seeds = [PLATFORM_SEED],
-        bump = platform.bump
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
     )]
     pub platform: Account<'info, PlatformAccount>,
 
@@ -117,6 +118,8 @@ pub fn handler(
     earnings.royalty_earnings = 0;
     earnings.last_withdrawal_at = None;
     earnings.withdrawal_count = 0;
+    earnings.payout_wallet = None;
+    earnings.payment_mint = None;
     earnings.bump = ctx.bumps.earnings; // 保存earnings PDA的bump值
 
     // 将场馆状态更新为激活状态
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::{
-    state::{VenueAccount, VenueStatus},
+    state::{VenueAccount, VenueStatus, PlatformAccount},
     error::TicketError,
+    constants::PLATFORM_SEED,
 };
 
 #[derive(Accounts)]
@@ -20,6 +21,14 @@ pub struct DeleteVenue<'info> {
         close = creator
     )]
     pub venue_account: Account<'info, VenueAccount>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, PlatformAccount>,
 }
 
 pub fn handler(
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{EventAccount, TicketTypeAccount, SeatStatusMap, SeatStatusPage, SeatStatus, SeatHold, PlatformAccount},
+    error::TicketError,
+    constants::*,
+};
+
+#[derive(Accounts)]
+#[instruction(ticket_type_name: String, area_id: String)]
+pub struct HoldSeats<'info> {
+    #[account(
+        seeds = [EVENT_SEED, event.organizer.as_ref(), event.event_name.as_bytes()],
+        bump = event.bump
+    )]
+    pub event: Box<Account<'info, EventAccount>>,
+
+    #[account(
+        seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Box<Account<'info, TicketTypeAccount>>,
+
+    /// 座位状态映射账户
+    #[account(
+        mut,
+        seeds = [b"seat_status_map", event.key().as_ref(), ticket_type.key().as_ref(), area_id.as_bytes()],
+        bump = seat_status_map.bump
+    )]
+    pub seat_status_map: Box<Account<'info, SeatStatusMap>>,
+
+    /// 座位所在的座位状态分页账户（同一次操作的座位需位于同一分页）
+    #[account(
+        mut,
+        constraint = seat_status_page.load()?.seat_status_map == seat_status_map.key() @ TicketError::InvalidAccount
+    )]
+    pub seat_status_page: AccountLoader<'info, SeatStatusPage>,
+
+    /// 选座锁定账户（每个买家在同一座位状态映射下仅有一个）
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + SeatHold::INIT_SPACE,
+        seeds = [SEAT_HOLD_SEED, seat_status_map.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub seat_hold: Box<Account<'info, SeatHold>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Box<Account<'info, PlatformAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<HoldSeats>,
+    _ticket_type_name: String,
+    _area_id: String,
+    seat_indices: Vec<u32>,
+    hold_duration: Option<i64>,
+) -> Result<()> {
+    let event = &ctx.accounts.event;
+    let seat_status_map = &mut ctx.accounts.seat_status_map;
+    let seat_hold = &mut ctx.accounts.seat_hold;
+    let current_time = Clock::get()?.unix_timestamp;
+    let mut seat_status_page = ctx.accounts.seat_status_page.load_mut()?;
+
+    // 验证售票时间
+    require!(
+        current_time >= event.ticket_sale_start_time,
+        TicketError::SaleNotStarted
+    );
+    require!(
+        current_time <= event.ticket_sale_end_time,
+        TicketError::SaleEnded
+    );
+
+    // 验证锁定座位数量和时长
+    require!(!seat_indices.is_empty(), TicketError::RequiredFieldEmpty);
+    require!(seat_indices.len() <= MAX_SEATS_PER_HOLD, TicketError::TooManySeats);
+    let hold_duration = hold_duration.unwrap_or(DEFAULT_SEAT_HOLD_DURATION);
+    require!(
+        hold_duration > 0 && hold_duration <= MAX_SEAT_HOLD_DURATION,
+        TicketError::InvalidHoldDuration
+    );
+
+    // 仅可锁定可售座位（重复的索引在第二次检查时已是锁定状态），同一次锁定的座位需位于同一分页
+    let mut prior_statuses = Vec::with_capacity(seat_indices.len());
+    for seat_index in seat_indices.iter() {
+        let status = seat_status_map.get_seat_status(&seat_status_page, *seat_index)?;
+        require!(status.is_purchasable(), TicketError::SeatNotAvailable);
+        prior_statuses.push(status);
+        seat_status_map.update_seat_status(
+            &mut seat_status_page,
+            *seat_index,
+            SeatStatus::TempLocked,
+        )?;
+    }
+
+    seat_hold.buyer = ctx.accounts.buyer.key();
+    seat_hold.seat_status_map = seat_status_map.key();
+    seat_hold.seat_indices = seat_indices;
+    seat_hold.prior_statuses = prior_statuses;
+    seat_hold.expires_at = current_time + hold_duration;
+    seat_hold.created_at = current_time;
+    seat_hold.bump = ctx.bumps.seat_hold;
+
+    msg!(
+        "座位锁定成功: 买家: {}, 座位数: {}, 到期时间: {}",
+        seat_hold.buyer,
+        seat_hold.seat_indices.len(),
+        seat_hold.expires_at
+    );
+
+    Ok(())
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 use crate::{
-    state::{TicketAccount, MarketplaceListingAccount, ListingStatus},
+    state::{EventAccount, TicketAccount, TicketStatus, TicketTypeAccount, PlatformAccount, MarketplaceListingAccount, ListingStatus},
     error::TicketError,
     constants::*,
+    utils::transfer_ticket_nft,
 };
 
 #[derive(Accounts)]
@@ -14,46 +19,134 @@ pub struct ListTicketForSale<'info> {
     )]
     pub ticket: Account<'info, TicketAccount>,
 
+    #[account(
+        seeds = [TICKET_TYPE_SEED, ticket.event.as_ref(), ticket.ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Account<'info, TicketTypeAccount>,
+
+    /// 门票所属活动（挂单到期时间不晚于活动开始时间）
+    #[account(address = ticket.event @ TicketError::InvalidAccount)]
+    pub event: Account<'info, EventAccount>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, PlatformAccount>,
+
     #[account(
         init,
         payer = seller,
-        space = MarketplaceListingAccount::INIT_SPACE,
+        space = 8 + MarketplaceListingAccount::INIT_SPACE,
         seeds = [MARKETPLACE_LISTING_SEED, ticket.mint.as_ref()],
         bump
     )]
     pub listing: Account<'info, MarketplaceListingAccount>,
 
+    /// 门票NFT的mint
+    #[account(address = ticket.mint @ TicketError::InvalidAccount)]
+    pub ticket_mint: InterfaceAccount<'info, Mint>,
+
+    /// 卖家的门票代币账户
+    #[account(
+        mut,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 挂单托管代币账户（由挂单PDA持有）
+    #[account(
+        init,
+        payer = seller,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
     #[account(mut)]
     pub seller: Signer<'info>,
+
+    /// 门票NFT所属的代币程序（SPL Token 或 Token-2022）
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<ListTicketForSale>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ListTicketForSale<'info>>,
     price: u64,
+    expires_at: Option<i64>,
 ) -> Result<()> {
     let ticket = &mut ctx.accounts.ticket;
     let listing = &mut ctx.accounts.listing;
     let clock = Clock::get()?;
 
-    // 验证门票状态
+    // 验证卖家持有门票（门票账户记录的所有者且持有门票NFT）
+    require!(
+        ticket.current_owner == ctx.accounts.seller.key(),
+        TicketError::NotTicketOwner
+    );
+    require!(
+        ctx.accounts.seller_token_account.amount == 1,
+        TicketError::NotTicketOwner
+    );
+
+    // 验证门票状态（已核销、已退款或已挂单的门票不能挂单）
+    require!(
+        ticket.current_status == TicketStatus::Sold,
+        TicketError::InvalidTicketStatus
+    );
     require!(
         ticket.transferable,
         TicketError::TicketNotTransferable
     );
+    require!(price > 0, TicketError::InvalidPrice);
+    ctx.accounts.ticket_type.validate_resale_price(ticket.original_price, price)?;
+
+    // 挂单到期时间：默认挂单30天，且不晚于活动开始时间
+    let event_start_time = ctx.accounts.event.event_start_time;
+    let expires_at = expires_at.unwrap_or_else(|| {
+        clock.unix_timestamp
+            .saturating_add(DEFAULT_LISTING_DURATION)
+            .min(event_start_time)
+    });
+    require!(
+        expires_at > clock.unix_timestamp && expires_at <= event_start_time,
+        TicketError::InvalidListingExpiry
+    );
+
+    // 将门票NFT转入挂单托管账户（Token-2022 门票的转账钩子额外账户通过 remaining_accounts 传入）
+    transfer_ticket_nft(
+        &ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.seller_token_account.to_account_info(),
+        ctx.accounts.ticket_mint.to_account_info(),
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.seller.to_account_info(),
+        ctx.remaining_accounts,
+        &[],
+    )?;
 
-    // 设置挂单信息并保存bump值
+    // 设置挂单信息并保存bump值，费率在挂单时锁定
     listing.ticket_mint = ticket.mint;
     listing.seller = ctx.accounts.seller.key();
     listing.price = price;
     listing.listed_at = clock.unix_timestamp;
+    listing.expires_at = expires_at;
     listing.status = ListingStatus::Active;
     listing.buyer = None;
     listing.sold_at = None;
     listing.sold_price = None;
-    listing.royalty_bps = 0; // 设置默认值
-    listing.platform_fee_bps = 250; // 2.5%
+    listing.royalty_bps = ctx.accounts.ticket_type.max_resale_royalty;
+    listing.platform_fee_bps = ctx.accounts.platform.platform_fee_bps;
     listing.bump = ctx.bumps.listing; // 保存listing PDA的bump值
 
+    // 挂单期间门票不能核销、退款或转让
+    ticket.current_status = TicketStatus::ListedForSale;
+
     Ok(())
-} 
\ No newline at end of file
+}
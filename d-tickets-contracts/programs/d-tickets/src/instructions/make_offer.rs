@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::{
+    state::{EventAccount, TicketTypeAccount, OrganizerEarnings, PlatformAccount, MarketplaceOfferAccount},
+    error::TicketError,
+    constants::*,
+    utils::Settlement,
+};
+
+#[derive(Accounts)]
+#[instruction(offer_id: u64)]
+pub struct MakeOffer<'info> {
+    #[account(
+        seeds = [TICKET_TYPE_SEED, ticket_type.event.as_ref(), ticket_type.type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Box<Account<'info, TicketTypeAccount>>,
+
+    /// 票种所属活动（出价到期时间不晚于活动开始时间）
+    #[account(address = ticket_type.event @ TicketError::InvalidAccount)]
+    pub event: Box<Account<'info, EventAccount>>,
+
+    /// 主办方收益账户（确定结算币种）
+    #[account(
+        seeds = [EARNINGS_SEED, ticket_type.event.as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Box<Account<'info, OrganizerEarnings>>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + MarketplaceOfferAccount::INIT_SPACE,
+        seeds = [MARKETPLACE_OFFER_SEED, buyer.key().as_ref(), ticket_type.key().as_ref(), &offer_id.to_le_bytes()],
+        bump
+    )]
+    pub offer: Box<Account<'info, MarketplaceOfferAccount>>,
+
+    /// 出价托管代币账户（SPL代币结算时必填，持有者为出价PDA，需在出价前创建）
+    #[account(mut)]
+    pub escrow_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 买家的支付代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub buyer_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Box<Account<'info, PlatformAccount>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// 支付结算使用的代币程序
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// 对指定门票或票种下任意门票出价，出价金额托管在出价PDA中
+pub fn handler(
+    ctx: Context<MakeOffer>,
+    offer_id: u64,
+    ticket_mint: Option<Pubkey>,
+    price: u64,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(price > 0, TicketError::InvalidPrice);
+
+    // 出价到期时间：默认30天，且不晚于活动开始时间
+    let event_start_time = ctx.accounts.event.event_start_time;
+    let expires_at = expires_at.unwrap_or_else(|| {
+        clock.unix_timestamp
+            .saturating_add(DEFAULT_LISTING_DURATION)
+            .min(event_start_time)
+    });
+    require!(
+        expires_at > clock.unix_timestamp && expires_at <= event_start_time,
+        TicketError::InvalidOfferExpiry
+    );
+
+    // 按活动结算币种（SOL或SPL代币）将出价金额转入托管
+    let payment_mint = ctx.accounts.earnings.payment_mint;
+    let settlement = Settlement::new(
+        payment_mint,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    );
+    let buyer = settlement.party(
+        ctx.accounts.buyer.to_account_info(),
+        ctx.accounts.buyer_payment_account.as_deref(),
+    )?;
+    let escrow = settlement.party(
+        ctx.accounts.offer.to_account_info(),
+        ctx.accounts.escrow_token_account.as_deref(),
+    )?;
+    settlement.require_balance(&buyer, price)?;
+    settlement.transfer(&buyer, &escrow, price)?;
+
+    // SPL代币托管账户的余额必须恰好等于出价金额
+    if let Some(escrow_token_account) = ctx.accounts.escrow_token_account.as_mut() {
+        escrow_token_account.reload()?;
+        require!(
+            escrow_token_account.amount == price,
+            TicketError::InvalidAccount
+        );
+    }
+
+    // 设置出价信息并保存bump值
+    let offer = &mut ctx.accounts.offer;
+    offer.buyer = ctx.accounts.buyer.key();
+    offer.event = ctx.accounts.event.key();
+    offer.ticket_type = ctx.accounts.ticket_type.key();
+    offer.ticket_mint = ticket_mint;
+    offer.offer_id = offer_id;
+    offer.price = price;
+    offer.payment_mint = payment_mint;
+    offer.created_at = clock.unix_timestamp;
+    offer.expires_at = expires_at;
+    offer.bump = ctx.bumps.offer;
+
+    msg!(
+        "出价成功: 买家: {}, 票种: {}, 价格: {}, 到期时间: {}",
+        offer.buyer,
+        ctx.accounts.ticket_type.type_name,
+        price,
+        expires_at
+    );
+
+    Ok(())
+}
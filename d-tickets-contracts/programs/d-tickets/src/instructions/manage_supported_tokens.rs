@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::{constants::*, error::TicketError, state::*};
+
+#[derive(Accounts)]
+pub struct AddSupportedToken<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        has_one = admin @ TicketError::NotPlatformAdmin
+    )]
+    pub platform: Account<'info, PlatformAccount>,
+
+    /// 新增的支付代币mint
+    pub token_mint: Account<'info, Mint>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveSupportedToken<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        has_one = admin @ TicketError::NotPlatformAdmin
+    )]
+    pub platform: Account<'info, PlatformAccount>,
+
+    pub admin: Signer<'info>,
+}
+
+/// 添加支持的支付代币
+pub fn add_supported_token(ctx: Context<AddSupportedToken>) -> Result<()> {
+    let platform = &mut ctx.accounts.platform;
+    let token_mint = ctx.accounts.token_mint.key();
+
+    require!(
+        !platform.is_supported_token(&token_mint),
+        TicketError::PaymentTokenAlreadySupported
+    );
+    require!(
+        platform.supported_tokens.len() < MAX_SUPPORTED_TOKENS,
+        TicketError::ArrayTooLong
+    );
+
+    platform.supported_tokens.push(token_mint);
+    platform.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("支付代币添加成功: {}", token_mint);
+
+    Ok(())
+}
+
+/// 移除支持的支付代币（已使用该代币定价的票种不受影响）
+pub fn remove_supported_token(
+    ctx: Context<RemoveSupportedToken>,
+    token_mint: Pubkey,
+) -> Result<()> {
+    let platform = &mut ctx.accounts.platform;
+
+    let index = platform
+        .supported_tokens
+        .iter()
+        .position(|mint| mint == &token_mint)
+        .ok_or(TicketError::UnsupportedPaymentToken)?;
+
+    platform.supported_tokens.remove(index);
+    platform.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("支付代币移除成功: {}", token_mint);
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{OrganizerEarnings, PlatformAccount, SeatAccount, TicketTypeAccount},
+    error::TicketError,
+    constants::*,
+    utils::transfer_lamports,
+};
+
+/// 迁移旧版账户布局：按当前结构扩容，新增字段追加在原有字段之后，扩容部分读取为默认值
+#[derive(Accounts)]
+pub struct MigrateAccountLayout<'info> {
+    /// CHECK: 待迁移的程序账户，按账户discriminator识别类型，旧版布局无法按当前结构反序列化
+    #[account(
+        mut,
+        owner = crate::ID @ TicketError::InvalidAccount
+    )]
+    pub target: UncheckedAccount<'info>,
+
+    /// 支付扩容所需租金
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, PlatformAccount>,
+}
+
+pub fn handler(ctx: Context<MigrateAccountLayout>) -> Result<()> {
+    let target = ctx.accounts.target.to_account_info();
+
+    // 按discriminator确定账户类型及当前布局所需空间
+    let new_space = {
+        let data = target.try_borrow_data()?;
+        require!(data.len() >= 8, TicketError::InvalidAccount);
+        let discriminator = &data[..8];
+        if discriminator == OrganizerEarnings::DISCRIMINATOR {
+            8 + OrganizerEarnings::INIT_SPACE
+        } else if discriminator == TicketTypeAccount::DISCRIMINATOR {
+            8 + TicketTypeAccount::INIT_SPACE
+        } else if discriminator == SeatAccount::DISCRIMINATOR {
+            8 + SeatAccount::INIT_SPACE
+        } else {
+            return err!(TicketError::InvalidAccount);
+        }
+    };
+
+    let current_space = target.data_len();
+    if current_space >= new_space {
+        msg!("账户布局已是最新: {}", target.key());
+        return Ok(());
+    }
+
+    // 扩容账户，由调用者补足租金
+    let rent_due = Rent::get()?
+        .minimum_balance(new_space)
+        .saturating_sub(target.lamports());
+    transfer_lamports(
+        ctx.accounts.payer.to_account_info(),
+        target.clone(),
+        ctx.accounts.system_program.to_account_info(),
+        rent_due,
+    )?;
+    target.realloc(new_space, true)?;
+
+    msg!(
+        "账户布局迁移成功: {}, 空间: {} -> {}",
+        target.key(),
+        current_space,
+        new_space
+    );
+
+    Ok(())
+}
@@ -19,6 +19,14 @@ pub struct MintTickets<'info> {
     
     #[account(mut)]
     pub organizer: Signer<'info>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, PlatformAccount>,
 }
 
 pub fn handler(
@@ -18,12 +18,24 @@ pub mod update_dynamic_pricing;
 
 // 购买和转移指令
 pub mod purchase_ticket;
+pub mod purchase_ticket_token_2022;
+pub mod purchase_tickets;
+pub mod purchase_seat;
+pub mod hold_seats;
+pub mod release_expired_holds;
 pub mod refund_ticket;
+pub mod approve_ticket_transfer;
+pub mod complete_ticket_transfer;
 
 // 二级市场指令
 pub mod list_ticket_for_sale;
 pub mod buy_ticket_from_market;
 pub mod cancel_ticket_listing;
+pub mod update_listing_price;
+pub mod close_expired_listing;
+pub mod make_offer;
+pub mod accept_offer;
+pub mod withdraw_offer;
 
 // 入场核销指令
 pub mod redeem_ticket;
@@ -31,6 +43,9 @@ pub mod redeem_ticket;
 // 管理指令
 pub mod setup_platform;
 pub mod withdraw_proceeds;
+pub mod set_payout_wallet;
+pub mod manage_supported_tokens;
+pub mod migrate_account_layout;
 
 // 重新导出所有公共结构
 pub use create_event::*;
@@ -46,10 +61,25 @@ pub use delete_venue::*;
 pub use configure_seats::*;
 pub use update_dynamic_pricing::*;
 pub use purchase_ticket::*;
+pub use purchase_ticket_token_2022::*;
+pub use purchase_tickets::*;
+pub use purchase_seat::*;
+pub use hold_seats::*;
+pub use release_expired_holds::*;
 pub use refund_ticket::*;
+pub use approve_ticket_transfer::*;
+pub use complete_ticket_transfer::*;
 pub use list_ticket_for_sale::*;
 pub use buy_ticket_from_market::*;
 pub use cancel_ticket_listing::*;
+pub use update_listing_price::*;
+pub use close_expired_listing::*;
+pub use make_offer::*;
+pub use accept_offer::*;
+pub use withdraw_offer::*;
 pub use redeem_ticket::*;
 pub use setup_platform::*;
 pub use withdraw_proceeds::*;
+pub use set_payout_wallet::*;
+pub use manage_supported_tokens::*;
+pub use migrate_account_layout::*;
@@ -0,0 +1,347 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::Metadata,
+    token::{Mint, Token, TokenAccount},
+};
+use crate::{
+    state::{EventAccount, TicketTypeAccount, TicketAccount, TicketStatus, OrganizerEarnings, PlatformAccount, WalletPurchaseRecord, SeatStatusMap, SeatStatusPage, SeatStatus, SeatHold, SeatLayoutAccount, SeatPriceTierAccount},
+    error::TicketError,
+    constants::*,
+    utils::{mint_ticket_nft, settle_primary_sale, ticket_metadata_hash, ticket_metadata_uri, ticket_nft_name, PrimarySaleAccounts, TicketNftAccounts},
+};
+
+#[derive(Accounts)]
+#[instruction(ticket_type_name: String, area_id: String)]
+pub struct PurchaseSeat<'info> {
+    #[account(
+        mut,
+        seeds = [EVENT_SEED, event.organizer.as_ref(), event.event_name.as_bytes()],
+        bump = event.bump
+    )]
+    pub event: Box<Account<'info, EventAccount>>,
+
+    #[account(
+        mut,
+        seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Box<Account<'info, TicketTypeAccount>>,
+
+    /// 座位状态映射账户（购买时原子地占用座位）
+    #[account(
+        mut,
+        seeds = [b"seat_status_map", event.key().as_ref(), ticket_type.key().as_ref(), area_id.as_bytes()],
+        bump = seat_status_map.bump
+    )]
+    pub seat_status_map: Box<Account<'info, SeatStatusMap>>,
+
+    /// 座位所在的座位状态分页账户（同一次操作的座位需位于同一分页）
+    #[account(
+        mut,
+        constraint = seat_status_page.load()?.seat_status_map == seat_status_map.key() @ TicketError::InvalidAccount
+    )]
+    pub seat_status_page: AccountLoader<'info, SeatStatusPage>,
+
+    /// 座位布局账户（区域已登记座位布局时必填）
+    #[account(
+        seeds = [SEAT_LAYOUT_SEED, seat_status_map.key().as_ref()],
+        bump = seat_layout.bump
+    )]
+    pub seat_layout: Option<Account<'info, SeatLayoutAccount>>,
+
+    /// 座位价格档位账户（区域已设置价格档位时必填）
+    #[account(
+        seeds = [SEAT_PRICE_TIERS_SEED, seat_status_map.key().as_ref()],
+        bump = seat_price_tiers.bump
+    )]
+    pub seat_price_tiers: Option<Account<'info, SeatPriceTierAccount>>,
+
+    /// 买家的选座锁定（购买已锁定的座位时必填，购买后消耗锁定）
+    #[account(
+        mut,
+        seeds = [SEAT_HOLD_SEED, seat_status_map.key().as_ref(), buyer.key().as_ref()],
+        bump = seat_hold.bump
+    )]
+    pub seat_hold: Option<Box<Account<'info, SeatHold>>>,
+
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Box<Account<'info, OrganizerEarnings>>,
+
+    /// 活动资金托管账户（PDA，接收主办方收入）
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref(), VAULT_SEED],
+        bump
+    )]
+    pub event_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Box<Account<'info, PlatformAccount>>,
+
+    /// CHECK: 平台收款账户，地址必须与 platform.fee_recipient 一致
+    #[account(
+        mut,
+        address = platform.fee_recipient @ TicketError::InvalidAccount
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: 门票NFT的铸造及冻结权限（程序PDA，不存储数据）
+    #[account(
+        seeds = [TICKET_MINT_AUTHORITY_SEED],
+        bump
+    )]
+    pub ticket_mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = buyer,
+        mint::decimals = 0,
+        mint::authority = ticket_mint_authority,
+        mint::freeze_authority = ticket_mint_authority,
+        seeds = [TICKET_MINT_SEED, event.key().as_ref(), &ticket_type_name.as_bytes(), &ticket_type.next_mint_index().to_le_bytes()],
+        bump
+    )]
+    pub ticket_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = buyer,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = buyer
+    )]
+    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: 门票NFT的元数据账户，由 Token Metadata 程序创建
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), ticket_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: 门票NFT的主版本账户，由 Token Metadata 程序创建
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), ticket_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + TicketAccount::INIT_SPACE,
+        seeds = [TICKET_SEED, ticket_mint.key().as_ref()],
+        bump
+    )]
+    pub ticket: Box<Account<'info, TicketAccount>>,
+
+    /// 买家在该票种下的购票记录（用于每钱包限购）
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + WalletPurchaseRecord::INIT_SPACE,
+        seeds = [PURCHASE_RECORD_SEED, ticket_type.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub purchase_record: Box<Account<'info, WalletPurchaseRecord>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// 买家的支付代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub buyer_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 活动的SPL代币托管账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 平台收款代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub fee_recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(
+    ctx: Context<PurchaseSeat>,
+    ticket_type_name: String,
+    area_id: String,
+    seat_index: u32,
+) -> Result<()> {
+    let seat_status_map_key = ctx.accounts.seat_status_map.key();
+    let event = &mut ctx.accounts.event;
+    let ticket_type = &mut ctx.accounts.ticket_type;
+    let ticket = &mut ctx.accounts.ticket;
+    let earnings = &mut ctx.accounts.earnings;
+    let platform = &mut ctx.accounts.platform;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // 验证活动状态和时间
+    require!(
+        current_time >= event.ticket_sale_start_time,
+        TicketError::SaleNotStarted
+    );
+    require!(
+        current_time <= event.ticket_sale_end_time,
+        TicketError::SaleEnded
+    );
+
+    // 验证库存
+    require!(
+        ticket_type.sold_count < ticket_type.total_supply,
+        TicketError::InsufficientTicketSupply
+    );
+
+    // 验证座位可售（可用、已退票，或由买家本人锁定且未过期），并在同一指令内标记为已售，避免同一座位被重复售出
+    let seat_status_map = &mut ctx.accounts.seat_status_map;
+    let mut seat_status_page = ctx.accounts.seat_status_page.load_mut()?;
+    match seat_status_map.get_seat_status(&seat_status_page, seat_index)? {
+        SeatStatus::Available | SeatStatus::Refunded => {}
+        SeatStatus::TempLocked => {
+            let seat_hold = ctx.accounts.seat_hold
+                .as_mut()
+                .ok_or(TicketError::SeatNotAvailable)?;
+            require!(
+                !seat_hold.is_expired(current_time),
+                TicketError::SeatHoldExpired
+            );
+            seat_hold.take_seat(seat_index)?;
+        }
+        _ => return err!(TicketError::SeatNotAvailable),
+    }
+    seat_status_map.update_seat_status(
+        &mut seat_status_page,
+        seat_index,
+        SeatStatus::Sold,
+    )?;
+
+    // 座位号：已登记布局时为 区域-排号-座位号，否则为 区域-座位序号（从1开始）
+    let seat_number = match ctx.accounts.seat_layout.as_deref() {
+        Some(seat_layout) => {
+            let (row_number, seat_number_in_row) = seat_layout.seat_label(seat_index)?;
+            format!("{}-{}-{}", area_id, row_number, seat_number_in_row)
+        }
+        None => {
+            require!(!seat_status_map.has_seat_layout, TicketError::SeatLayoutRequired);
+            format!("{}-{}", area_id, seat_index + 1)
+        }
+    };
+    require!(
+        seat_number.len() <= MAX_SEAT_NUMBER_LENGTH,
+        TicketError::SeatNumberTooLong
+    );
+
+    // 验证每钱包限购
+    let purchase_record = &mut ctx.accounts.purchase_record;
+    purchase_record.buyer = ctx.accounts.buyer.key();
+    purchase_record.ticket_type = ticket_type.key();
+    purchase_record.bump = ctx.bumps.purchase_record;
+    purchase_record.record_purchase(1, ticket_type.max_per_wallet)?;
+
+    // 计算票价（已设置价格档位时按座位所在档位计价）
+    let ticket_price = seat_status_map.seat_price(
+        ctx.accounts.seat_price_tiers.as_deref(),
+        ticket_type.current_price,
+        seat_index,
+    )?;
+
+    // 按活动结算币种（SOL或SPL代币）付款，并更新活动、收益和平台统计
+    let organizer_amount = settle_primary_sale(
+        &PrimarySaleAccounts {
+            buyer: ctx.accounts.buyer.to_account_info(),
+            buyer_payment_account: ctx.accounts.buyer_payment_account.as_deref(),
+            event_vault: ctx.accounts.event_vault.to_account_info(),
+            vault_token_account: ctx.accounts.vault_token_account.as_deref(),
+            fee_recipient: ctx.accounts.fee_recipient.to_account_info(),
+            fee_recipient_token_account: ctx.accounts.fee_recipient_token_account.as_deref(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        ticket_price,
+        1,
+        event,
+        earnings,
+        platform,
+    )?;
+
+    // 由程序PDA铸造NFT并创建元数据和主版本（供应量固定为1）
+    mint_ticket_nft(
+        &TicketNftAccounts {
+            mint: ctx.accounts.ticket_mint.to_account_info(),
+            token_account: ctx.accounts.buyer_token_account.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            master_edition: ctx.accounts.master_edition.to_account_info(),
+            mint_authority: ctx.accounts.ticket_mint_authority.to_account_info(),
+            payer: ctx.accounts.buyer.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        ctx.bumps.ticket_mint_authority,
+        ticket_nft_name(&event.event_name, &ticket_type_name, &seat_number),
+        ticket_metadata_uri(&event.event_description_hash, ticket_type.next_mint_index() + 1),
+        event.organizer,
+        ticket_type.max_resale_royalty,
+    )?;
+
+    // 设置门票账户并保存bump值
+    ticket.event = event.key();
+    ticket.ticket_type_name = ticket_type_name;
+    ticket.mint = ctx.accounts.ticket_mint.key();
+    ticket.current_owner = ctx.accounts.buyer.key();
+    ticket.original_buyer = ctx.accounts.buyer.key();
+    ticket.seat_number = Some(seat_number);
+    ticket.seat_status_map = Some(seat_status_map_key);
+    ticket.seat_index = Some(seat_index);
+    ticket.original_price = ticket_price;
+    ticket.organizer_amount = organizer_amount;
+    ticket.current_status = TicketStatus::Sold;
+    ticket.purchased_at = current_time;
+    ticket.redeemed_at = None;
+    ticket.metadata_hash = ticket_metadata_hash(&event.event_name, ticket_type.next_mint_index() + 1);
+    ticket.transferable = true;
+    ticket.transfer_count = 0;
+    ticket.last_transfer_at = None;
+    ticket.bump = ctx.bumps.ticket; // 保存ticket PDA的bump值
+
+    // 更新票种已售数量
+    ticket_type.sold_count += 1;
+
+    // 更新时间戳
+    event.updated_at = current_time;
+
+    // 锁定的座位全部购买后关闭锁定账户，租金返还买家
+    if let Some(seat_hold) = &ctx.accounts.seat_hold {
+        if seat_hold.seat_indices.is_empty() {
+            seat_hold.close(ctx.accounts.buyer.to_account_info())?;
+        }
+    }
+
+    msg!(
+        "对号入座购票成功: 票种: {}, 区域: {}, 座位索引: {}",
+        ticket.ticket_type_name,
+        area_id,
+        seat_index
+    );
+
+    Ok(())
+} 
\ No newline at end of file
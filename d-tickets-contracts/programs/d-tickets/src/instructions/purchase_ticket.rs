@@ -1,12 +1,14 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, MintTo},
+    metadata::Metadata,
+    token::{Mint, Token, TokenAccount},
 };
 use crate::{
-    state::{EventAccount, TicketTypeAccount, TicketAccount, TicketStatus, OrganizerEarnings, PlatformAccount},
+    state::{EventAccount, TicketTypeAccount, TicketAccount, TicketStatus, OrganizerEarnings, PlatformAccount, WalletPurchaseRecord},
     error::TicketError,
     constants::*,
+    utils::{mint_ticket_nft, settle_primary_sale, ticket_metadata_hash, ticket_metadata_uri, ticket_nft_name, PrimarySaleAccounts, TicketNftAccounts},
 };
 
 #[derive(Accounts)]
@@ -17,38 +19,62 @@ pub struct PurchaseTicket<'info> {
         seeds = [EVENT_SEED, event.organizer.as_ref(), event.event_name.as_bytes()],
         bump = event.bump
     )]
-    pub event: Account<'info, EventAccount>,
+    pub event: Box<Account<'info, EventAccount>>,
 
     #[account(
         mut,
         seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
         bump = ticket_type.bump
     )]
-    pub ticket_type: Account<'info, TicketTypeAccount>,
+    pub ticket_type: Box<Account<'info, TicketTypeAccount>>,
 
     #[account(
         mut,
         seeds = [EARNINGS_SEED, event.key().as_ref()],
         bump = earnings.bump
     )]
-    pub earnings: Account<'info, OrganizerEarnings>,
+    pub earnings: Box<Account<'info, OrganizerEarnings>>,
 
+    /// 活动资金托管账户（PDA，接收主办方收入）
     #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref(), VAULT_SEED],
+        bump
+    )]
+    pub event_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
         seeds = [PLATFORM_SEED],
-        bump = platform.bump
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Box<Account<'info, PlatformAccount>>,
+
+    /// CHECK: 平台收款账户，地址必须与 platform.fee_recipient 一致
+    #[account(
+        mut,
+        address = platform.fee_recipient @ TicketError::InvalidAccount
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: 门票NFT的铸造及冻结权限（程序PDA，不存储数据）
+    #[account(
+        seeds = [TICKET_MINT_AUTHORITY_SEED],
+        bump
     )]
-    pub platform: Account<'info, PlatformAccount>,
+    pub ticket_mint_authority: UncheckedAccount<'info>,
 
     #[account(
         init,
         payer = buyer,
         mint::decimals = 0,
-        mint::authority = buyer,
-        mint::freeze_authority = buyer,
-        seeds = [TICKET_MINT_SEED, event.key().as_ref(), &ticket_type_name.as_bytes(), &ticket_type.sold_count.to_le_bytes()],
+        mint::authority = ticket_mint_authority,
+        mint::freeze_authority = ticket_mint_authority,
+        seeds = [TICKET_MINT_SEED, event.key().as_ref(), &ticket_type_name.as_bytes(), &ticket_type.next_mint_index().to_le_bytes()],
         bump
     )]
-    pub ticket_mint: Account<'info, Mint>,
+    pub ticket_mint: Box<Account<'info, Mint>>,
 
     #[account(
         init,
@@ -56,22 +82,63 @@ pub struct PurchaseTicket<'info> {
         associated_token::mint = ticket_mint,
         associated_token::authority = buyer
     )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+    pub buyer_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: 门票NFT的元数据账户，由 Token Metadata 程序创建
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), ticket_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: 门票NFT的主版本账户，由 Token Metadata 程序创建
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, token_metadata_program.key().as_ref(), ticket_mint.key().as_ref(), EDITION_SEED],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub master_edition: UncheckedAccount<'info>,
 
     #[account(
         init,
         payer = buyer,
-        space = TicketAccount::INIT_SPACE,
+        space = 8 + TicketAccount::INIT_SPACE,
         seeds = [TICKET_SEED, ticket_mint.key().as_ref()],
         bump
     )]
-    pub ticket: Account<'info, TicketAccount>,
+    pub ticket: Box<Account<'info, TicketAccount>>,
+
+    /// 买家在该票种下的购票记录（用于每钱包限购）
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + WalletPurchaseRecord::INIT_SPACE,
+        seeds = [PURCHASE_RECORD_SEED, ticket_type.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub purchase_record: Box<Account<'info, WalletPurchaseRecord>>,
 
     #[account(mut)]
     pub buyer: Signer<'info>,
 
+    /// 买家的支付代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub buyer_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 活动的SPL代币托管账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 平台收款代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub fee_recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -84,7 +151,7 @@ pub fn handler(
     let ticket_type = &mut ctx.accounts.ticket_type;
     let ticket = &mut ctx.accounts.ticket;
     let earnings = &mut ctx.accounts.earnings;
-    let platform = &ctx.accounts.platform;
+    let platform = &mut ctx.accounts.platform;
     let current_time = Clock::get()?.unix_timestamp;
 
     // 验证活动状态和时间
@@ -103,31 +170,56 @@ pub fn handler(
         TicketError::InsufficientTicketSupply
     );
 
-    // 计算费用
+    // 验证每钱包限购
+    let purchase_record = &mut ctx.accounts.purchase_record;
+    purchase_record.buyer = ctx.accounts.buyer.key();
+    purchase_record.ticket_type = ticket_type.key();
+    purchase_record.bump = ctx.bumps.purchase_record;
+    purchase_record.record_purchase(1, ticket_type.max_per_wallet)?;
+
+    // 计算票价
     let ticket_price = ticket_type.current_price;
-    let platform_fee = ticket_price
-        .checked_mul(platform.platform_fee_bps as u64)
-        .unwrap()
-        .checked_div(10000)
-        .unwrap();
-    let organizer_amount = ticket_price.checked_sub(platform_fee).unwrap();
-
-    // 验证支付金额（这里简化，实际应该处理SOL转账）
-    require!(
-        ctx.accounts.buyer.lamports() >= ticket_price,
-        TicketError::InsufficientPayment
-    );
 
-    // 铸造NFT
-    let cpi_accounts = MintTo {
-        mint: ctx.accounts.ticket_mint.to_account_info(),
-        to: ctx.accounts.buyer_token_account.to_account_info(),
-        authority: ctx.accounts.buyer.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    
-    token::mint_to(cpi_ctx, 1)?;
+    // 按活动结算币种（SOL或SPL代币）付款，并更新活动、收益和平台统计
+    let organizer_amount = settle_primary_sale(
+        &PrimarySaleAccounts {
+            buyer: ctx.accounts.buyer.to_account_info(),
+            buyer_payment_account: ctx.accounts.buyer_payment_account.as_deref(),
+            event_vault: ctx.accounts.event_vault.to_account_info(),
+            vault_token_account: ctx.accounts.vault_token_account.as_deref(),
+            fee_recipient: ctx.accounts.fee_recipient.to_account_info(),
+            fee_recipient_token_account: ctx.accounts.fee_recipient_token_account.as_deref(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        ticket_price,
+        1,
+        event,
+        earnings,
+        platform,
+    )?;
+
+    // 由程序PDA铸造NFT并创建元数据和主版本（供应量固定为1）
+    let seat_number = format!("SEAT-{}", ticket_type.next_mint_index() + 1);
+    mint_ticket_nft(
+        &TicketNftAccounts {
+            mint: ctx.accounts.ticket_mint.to_account_info(),
+            token_account: ctx.accounts.buyer_token_account.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            master_edition: ctx.accounts.master_edition.to_account_info(),
+            mint_authority: ctx.accounts.ticket_mint_authority.to_account_info(),
+            payer: ctx.accounts.buyer.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        ctx.bumps.ticket_mint_authority,
+        ticket_nft_name(&event.event_name, &ticket_type_name, &seat_number),
+        ticket_metadata_uri(&event.event_description_hash, ticket_type.next_mint_index() + 1),
+        event.organizer,
+        ticket_type.max_resale_royalty,
+    )?;
 
     // 设置门票账户并保存bump值
     ticket.event = event.key();
@@ -135,23 +227,22 @@ pub fn handler(
     ticket.mint = ctx.accounts.ticket_mint.key();
     ticket.current_owner = ctx.accounts.buyer.key();
     ticket.original_buyer = ctx.accounts.buyer.key();
-    ticket.seat_number = Some(format!("SEAT-{}", ticket_type.sold_count + 1));
+    ticket.seat_number = Some(seat_number);
+    ticket.seat_status_map = None;
+    ticket.seat_index = None;
     ticket.original_price = ticket_price;
+    ticket.organizer_amount = organizer_amount;
     ticket.current_status = TicketStatus::Sold;
     ticket.purchased_at = current_time;
     ticket.redeemed_at = None;
-    ticket.metadata_hash = format!("ticket-{}-{}", event.event_name, ticket_type.sold_count + 1);
+    ticket.metadata_hash = ticket_metadata_hash(&event.event_name, ticket_type.next_mint_index() + 1);
     ticket.transferable = true;
     ticket.transfer_count = 0;
     ticket.last_transfer_at = None;
     ticket.bump = ctx.bumps.ticket; // 保存ticket PDA的bump值
 
-    // 更新统计数据
+    // 更新票种已售数量
     ticket_type.sold_count += 1;
-    event.total_tickets_sold += 1;
-    event.total_revenue += ticket_price;
-    earnings.total_earnings += organizer_amount;
-    earnings.pending_amount += organizer_amount;
 
     // 更新时间戳
     event.updated_at = current_time;
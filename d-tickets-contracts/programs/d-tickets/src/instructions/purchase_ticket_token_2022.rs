@@ -0,0 +1,254 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Token, TokenAccount},
+    token_2022::Token2022,
+    token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface},
+};
+use crate::{
+    state::{EventAccount, TicketTypeAccount, TicketAccount, TicketStatus, OrganizerEarnings, PlatformAccount, WalletPurchaseRecord},
+    error::TicketError,
+    constants::*,
+    utils::{mint_ticket_nft_2022, settle_primary_sale, ticket_metadata_hash, ticket_metadata_uri, ticket_nft_name, PrimarySaleAccounts, TicketNft2022Accounts},
+};
+
+#[derive(Accounts)]
+#[instruction(ticket_type_name: String)]
+pub struct PurchaseTicketToken2022<'info> {
+    #[account(
+        mut,
+        seeds = [EVENT_SEED, event.organizer.as_ref(), event.event_name.as_bytes()],
+        bump = event.bump
+    )]
+    pub event: Box<Account<'info, EventAccount>>,
+
+    #[account(
+        mut,
+        seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Box<Account<'info, TicketTypeAccount>>,
+
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Box<Account<'info, OrganizerEarnings>>,
+
+    /// 活动资金托管账户（PDA，接收主办方收入）
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref(), VAULT_SEED],
+        bump
+    )]
+    pub event_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Box<Account<'info, PlatformAccount>>,
+
+    /// CHECK: 平台收款账户，地址必须与 platform.fee_recipient 一致
+    #[account(
+        mut,
+        address = platform.fee_recipient @ TicketError::InvalidAccount
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: 门票NFT的铸造、冻结及转账钩子权限（程序PDA，不存储数据）
+    #[account(
+        seeds = [TICKET_MINT_AUTHORITY_SEED],
+        bump
+    )]
+    pub ticket_mint_authority: UncheckedAccount<'info>,
+
+    /// Token-2022 门票mint，元数据存放在mint自身，转账时回调转账钩子程序
+    #[account(
+        init,
+        payer = buyer,
+        mint::decimals = 0,
+        mint::authority = ticket_mint_authority,
+        mint::freeze_authority = ticket_mint_authority,
+        mint::token_program = ticket_token_program,
+        extensions::metadata_pointer::authority = ticket_mint_authority,
+        extensions::metadata_pointer::metadata_address = ticket_mint,
+        extensions::transfer_hook::authority = ticket_mint_authority,
+        extensions::transfer_hook::program_id = transfer_hook_program,
+        seeds = [TICKET_MINT_SEED, event.key().as_ref(), &ticket_type_name.as_bytes(), &ticket_type.next_mint_index().to_le_bytes()],
+        bump
+    )]
+    pub ticket_mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    #[account(
+        init,
+        payer = buyer,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = ticket_token_program
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// CHECK: 转账钩子的额外账户列表，由转账钩子程序创建
+    #[account(
+        mut,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, ticket_mint.key().as_ref()],
+        bump,
+        seeds::program = transfer_hook_program.key()
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + TicketAccount::INIT_SPACE,
+        seeds = [TICKET_SEED, ticket_mint.key().as_ref()],
+        bump
+    )]
+    pub ticket: Box<Account<'info, TicketAccount>>,
+
+    /// 买家在该票种下的购票记录（用于每钱包限购）
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + WalletPurchaseRecord::INIT_SPACE,
+        seeds = [PURCHASE_RECORD_SEED, ticket_type.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub purchase_record: Box<Account<'info, WalletPurchaseRecord>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// 买家的支付代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub buyer_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 活动的SPL代币托管账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 平台收款代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub fee_recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: 门票转账钩子程序
+    #[account(
+        executable,
+        address = TRANSFER_HOOK_PROGRAM_ID @ TicketError::InvalidAccount
+    )]
+    pub transfer_hook_program: UncheckedAccount<'info>,
+
+    /// 支付结算使用的代币程序
+    pub token_program: Program<'info, Token>,
+    /// 门票NFT使用的 Token-2022 程序
+    pub ticket_token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<PurchaseTicketToken2022>,
+    ticket_type_name: String,
+) -> Result<()> {
+    let event = &mut ctx.accounts.event;
+    let ticket_type = &mut ctx.accounts.ticket_type;
+    let ticket = &mut ctx.accounts.ticket;
+    let earnings = &mut ctx.accounts.earnings;
+    let platform = &mut ctx.accounts.platform;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // 验证活动状态和时间
+    require!(
+        current_time >= event.ticket_sale_start_time,
+        TicketError::SaleNotStarted
+    );
+    require!(
+        current_time <= event.ticket_sale_end_time,
+        TicketError::SaleEnded
+    );
+
+    // 验证库存
+    require!(
+        ticket_type.sold_count < ticket_type.total_supply,
+        TicketError::InsufficientTicketSupply
+    );
+
+    // 验证每钱包限购
+    let purchase_record = &mut ctx.accounts.purchase_record;
+    purchase_record.buyer = ctx.accounts.buyer.key();
+    purchase_record.ticket_type = ticket_type.key();
+    purchase_record.bump = ctx.bumps.purchase_record;
+    purchase_record.record_purchase(1, ticket_type.max_per_wallet)?;
+
+    // 计算票价
+    let ticket_price = ticket_type.current_price;
+
+    // 按活动结算币种（SOL或SPL代币）付款，并更新活动、收益和平台统计
+    let organizer_amount = settle_primary_sale(
+        &PrimarySaleAccounts {
+            buyer: ctx.accounts.buyer.to_account_info(),
+            buyer_payment_account: ctx.accounts.buyer_payment_account.as_deref(),
+            event_vault: ctx.accounts.event_vault.to_account_info(),
+            vault_token_account: ctx.accounts.vault_token_account.as_deref(),
+            fee_recipient: ctx.accounts.fee_recipient.to_account_info(),
+            fee_recipient_token_account: ctx.accounts.fee_recipient_token_account.as_deref(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        ticket_price,
+        1,
+        event,
+        earnings,
+        platform,
+    )?;
+
+    // 由程序PDA铸造 Token-2022 门票NFT（元数据写入mint，启用转账钩子）
+    let seat_number = format!("SEAT-{}", ticket_type.next_mint_index() + 1);
+    mint_ticket_nft_2022(
+        &TicketNft2022Accounts {
+            mint: ctx.accounts.ticket_mint.to_account_info(),
+            token_account: ctx.accounts.buyer_token_account.to_account_info(),
+            mint_authority: ctx.accounts.ticket_mint_authority.to_account_info(),
+            payer: ctx.accounts.buyer.to_account_info(),
+            extra_account_meta_list: ctx.accounts.extra_account_meta_list.to_account_info(),
+            token_program: ctx.accounts.ticket_token_program.to_account_info(),
+            transfer_hook_program: ctx.accounts.transfer_hook_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        ctx.bumps.ticket_mint_authority,
+        ticket_nft_name(&event.event_name, &ticket_type_name, &seat_number),
+        ticket_metadata_uri(&event.event_description_hash, ticket_type.next_mint_index() + 1),
+    )?;
+
+    // 设置门票账户并保存bump值
+    ticket.event = event.key();
+    ticket.ticket_type_name = ticket_type_name;
+    ticket.mint = ctx.accounts.ticket_mint.key();
+    ticket.current_owner = ctx.accounts.buyer.key();
+    ticket.original_buyer = ctx.accounts.buyer.key();
+    ticket.seat_number = Some(seat_number);
+    ticket.seat_status_map = None;
+    ticket.seat_index = None;
+    ticket.original_price = ticket_price;
+    ticket.organizer_amount = organizer_amount;
+    ticket.current_status = TicketStatus::Sold;
+    ticket.purchased_at = current_time;
+    ticket.redeemed_at = None;
+    ticket.metadata_hash = ticket_metadata_hash(&event.event_name, ticket_type.next_mint_index() + 1);
+    ticket.transferable = true;
+    ticket.transfer_count = 0;
+    ticket.last_transfer_at = None;
+    ticket.bump = ctx.bumps.ticket; // 保存ticket PDA的bump值
+
+    // 更新票种已售数量
+    ticket_type.sold_count += 1;
+
+    // 更新时间戳
+    event.updated_at = current_time;
+
+    Ok(())
+}
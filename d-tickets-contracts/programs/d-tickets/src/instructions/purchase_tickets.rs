@@ -0,0 +1,301 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::{
+    associated_token::{self, AssociatedToken, Create},
+    metadata::Metadata,
+    token::{self, spl_token, InitializeMint2, Token, TokenAccount},
+};
+use crate::{
+    state::{EventAccount, TicketTypeAccount, TicketAccount, TicketStatus, OrganizerEarnings, PlatformAccount, WalletPurchaseRecord},
+    error::TicketError,
+    constants::*,
+    utils::{create_pda_account, mint_ticket_nft, settle_primary_sale, ticket_metadata_hash, ticket_metadata_uri, ticket_nft_name, PrimarySaleAccounts, TicketNftAccounts},
+};
+
+/// 每张门票在 remaining_accounts 中占用的账户数：
+/// [门票mint, 买家代币账户, 元数据账户, 主版本账户, 门票账户]
+pub const ACCOUNTS_PER_TICKET: usize = 5;
+
+#[derive(Accounts)]
+#[instruction(ticket_type_name: String)]
+pub struct PurchaseTickets<'info> {
+    #[account(
+        mut,
+        seeds = [EVENT_SEED, event.organizer.as_ref(), event.event_name.as_bytes()],
+        bump = event.bump
+    )]
+    pub event: Box<Account<'info, EventAccount>>,
+
+    #[account(
+        mut,
+        seeds = [TICKET_TYPE_SEED, event.key().as_ref(), ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Box<Account<'info, TicketTypeAccount>>,
+
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Box<Account<'info, OrganizerEarnings>>,
+
+    /// 活动资金托管账户（PDA，接收主办方收入）
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref(), VAULT_SEED],
+        bump
+    )]
+    pub event_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Box<Account<'info, PlatformAccount>>,
+
+    /// CHECK: 平台收款账户，地址必须与 platform.fee_recipient 一致
+    #[account(
+        mut,
+        address = platform.fee_recipient @ TicketError::InvalidAccount
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: 门票NFT的铸造及冻结权限（程序PDA，不存储数据）
+    #[account(
+        seeds = [TICKET_MINT_AUTHORITY_SEED],
+        bump
+    )]
+    pub ticket_mint_authority: UncheckedAccount<'info>,
+
+    /// 买家在该票种下的购票记录（用于每钱包限购）
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + WalletPurchaseRecord::INIT_SPACE,
+        seeds = [PURCHASE_RECORD_SEED, ticket_type.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub purchase_record: Box<Account<'info, WalletPurchaseRecord>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// 买家的支付代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub buyer_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 活动的SPL代币托管账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 平台收款代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub fee_recipient_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// 一次购买多张门票，每张门票的账户按 ACCOUNTS_PER_TICKET 的顺序通过 remaining_accounts 传入
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, PurchaseTickets<'info>>,
+    ticket_type_name: String,
+    quantity: u32,
+) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let event = &mut ctx.accounts.event;
+    let ticket_type = &mut ctx.accounts.ticket_type;
+    let earnings = &mut ctx.accounts.earnings;
+    let platform = &mut ctx.accounts.platform;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // 验证购买数量及对应的账户数量
+    require!(quantity > 0, TicketError::InvalidPurchaseQuantity);
+    require!(
+        quantity <= MAX_TICKETS_PER_PURCHASE,
+        TicketError::ExceedsPurchaseLimit
+    );
+    require!(
+        ctx.remaining_accounts.len() == quantity as usize * ACCOUNTS_PER_TICKET,
+        TicketError::InvalidAccount
+    );
+
+    // 验证活动状态和时间
+    require!(
+        current_time >= event.ticket_sale_start_time,
+        TicketError::SaleNotStarted
+    );
+    require!(
+        current_time <= event.ticket_sale_end_time,
+        TicketError::SaleEnded
+    );
+
+    // 验证库存
+    require!(
+        quantity <= ticket_type.total_supply.saturating_sub(ticket_type.sold_count),
+        TicketError::InsufficientTicketSupply
+    );
+
+    // 验证每钱包限购
+    let purchase_record = &mut ctx.accounts.purchase_record;
+    purchase_record.buyer = ctx.accounts.buyer.key();
+    purchase_record.ticket_type = ticket_type.key();
+    purchase_record.bump = ctx.bumps.purchase_record;
+    purchase_record.record_purchase(quantity, ticket_type.max_per_wallet)?;
+
+    // 计算票价
+    let ticket_price = ticket_type.current_price;
+    let total_price = ticket_price
+        .checked_mul(quantity as u64)
+        .ok_or(TicketError::Overflow)?;
+    // 按活动结算币种（SOL或SPL代币）一次性付款，并更新活动、收益和平台统计
+    let organizer_amount = settle_primary_sale(
+        &PrimarySaleAccounts {
+            buyer: ctx.accounts.buyer.to_account_info(),
+            buyer_payment_account: ctx.accounts.buyer_payment_account.as_deref(),
+            event_vault: ctx.accounts.event_vault.to_account_info(),
+            vault_token_account: ctx.accounts.vault_token_account.as_deref(),
+            fee_recipient: ctx.accounts.fee_recipient.to_account_info(),
+            fee_recipient_token_account: ctx.accounts.fee_recipient_token_account.as_deref(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        total_price,
+        quantity,
+        event,
+        earnings,
+        platform,
+    )?;
+    // 每张门票记录的托管金额按数量平均分摊（向下取整），退款总额不会超过托管账户实际收到的金额
+    let organizer_amount_per_ticket = organizer_amount / quantity as u64;
+
+    let buyer_info = ctx.accounts.buyer.to_account_info();
+    let mint_authority = ctx.accounts.ticket_mint_authority.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    // 逐张创建门票mint、买家代币账户和门票账户，并铸造NFT
+    for ticket_accounts in ctx.remaining_accounts.chunks_exact(ACCOUNTS_PER_TICKET) {
+        let [ticket_mint, buyer_token_account, metadata, master_edition, ticket] = ticket_accounts else {
+            return err!(TicketError::InvalidAccount);
+        };
+        let mint_index = ticket_type.next_mint_index();
+        let mint_index_bytes = mint_index.to_le_bytes();
+
+        // 门票mint（PDA，与单张购买使用相同的种子）
+        let (mint_key, mint_bump) = Pubkey::find_program_address(
+            &[TICKET_MINT_SEED, event_key.as_ref(), ticket_type_name.as_bytes(), &mint_index_bytes],
+            ctx.program_id,
+        );
+        require_keys_eq!(ticket_mint.key(), mint_key, TicketError::InvalidAccount);
+        create_pda_account(
+            buyer_info.clone(),
+            ticket_mint.clone(),
+            system_program.clone(),
+            spl_token::state::Mint::LEN,
+            &token_program.key(),
+            &[TICKET_MINT_SEED, event_key.as_ref(), ticket_type_name.as_bytes(), &mint_index_bytes, &[mint_bump]],
+        )?;
+        token::initialize_mint2(
+            CpiContext::new(
+                token_program.clone(),
+                InitializeMint2 { mint: ticket_mint.clone() },
+            ),
+            0,
+            &mint_authority.key(),
+            Some(&mint_authority.key()),
+        )?;
+
+        // 买家的关联代币账户（地址由关联代币程序校验）
+        associated_token::create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            Create {
+                payer: buyer_info.clone(),
+                associated_token: buyer_token_account.clone(),
+                authority: buyer_info.clone(),
+                mint: ticket_mint.clone(),
+                system_program: system_program.clone(),
+                token_program: token_program.clone(),
+            },
+        ))?;
+
+        // 由程序PDA铸造NFT并创建元数据和主版本（元数据地址由 Token Metadata 程序校验）
+        let seat_number = format!("SEAT-{}", mint_index + 1);
+        mint_ticket_nft(
+            &TicketNftAccounts {
+                mint: ticket_mint.clone(),
+                token_account: buyer_token_account.clone(),
+                metadata: metadata.clone(),
+                master_edition: master_edition.clone(),
+                mint_authority: mint_authority.clone(),
+                payer: buyer_info.clone(),
+                token_program: token_program.clone(),
+                token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+                system_program: system_program.clone(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            ctx.bumps.ticket_mint_authority,
+            ticket_nft_name(&event.event_name, &ticket_type_name, &seat_number),
+            ticket_metadata_uri(&event.event_description_hash, mint_index + 1),
+            event.organizer,
+            ticket_type.max_resale_royalty,
+        )?;
+
+        // 门票账户（PDA）
+        let (ticket_key, ticket_bump) = Pubkey::find_program_address(
+            &[TICKET_SEED, mint_key.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(ticket.key(), ticket_key, TicketError::InvalidAccount);
+        create_pda_account(
+            buyer_info.clone(),
+            ticket.clone(),
+            system_program.clone(),
+            8 + TicketAccount::INIT_SPACE,
+            ctx.program_id,
+            &[TICKET_SEED, mint_key.as_ref(), &[ticket_bump]],
+        )?;
+        let ticket_data = TicketAccount {
+            event: event_key,
+            ticket_type_name: ticket_type_name.clone(),
+            mint: mint_key,
+            current_owner: buyer_info.key(),
+            original_buyer: buyer_info.key(),
+            seat_number: Some(seat_number),
+            original_price: ticket_price,
+            current_status: TicketStatus::Sold,
+            purchased_at: current_time,
+            redeemed_at: None,
+            metadata_hash: ticket_metadata_hash(&event.event_name, mint_index + 1),
+            transferable: true,
+            transfer_count: 0,
+            last_transfer_at: None,
+            bump: ticket_bump,
+            seat_status_map: None,
+            seat_index: None,
+            organizer_amount: organizer_amount_per_ticket,
+            _reserved: [0; 21],
+        };
+        ticket_data.try_serialize(&mut &mut ticket.try_borrow_mut_data()?[..])?;
+
+        ticket_type.sold_count += 1;
+    }
+
+    // 更新时间戳
+    event.updated_at = current_time;
+
+    msg!(
+        "批量购票成功: 票种: {}, 数量: {}, 总价: {}",
+        ticket_type_name,
+        quantity,
+        total_price
+    );
+
+    Ok(())
+}
@@ -21,6 +21,14 @@ pub struct RedeemTicket<'info> {
         constraint = validator.key() == event.organizer @ TicketError::NotAuthorizedValidator
     )]
     pub validator: Signer<'info>,
+
+    /// 座位状态映射账户（对号入座门票必填，核销后标记座位为已核销）
+    #[account(mut)]
+    pub seat_status_map: Option<Account<'info, SeatStatusMap>>,
+
+    /// 座位所在的座位状态分页账户（对号入座门票必填）
+    #[account(mut)]
+    pub seat_status_page: Option<AccountLoader<'info, SeatStatusPage>>,
 }
 
 pub fn handler(ctx: Context<RedeemTicket>) -> Result<()> {
@@ -49,6 +57,26 @@ pub fn handler(ctx: Context<RedeemTicket>) -> Result<()> {
         TicketError::TicketAlreadyRedeemed
     );
 
+    // 对号入座门票同步座位状态
+    if let Some(seat_index) = ticket.seat_index {
+        let seat_status_map = ctx.accounts.seat_status_map
+            .as_mut()
+            .ok_or(TicketError::InvalidAccount)?;
+        require!(
+            ticket.seat_status_map == Some(seat_status_map.key()),
+            TicketError::InvalidAccount
+        );
+        let mut seat_status_page = ctx.accounts.seat_status_page
+            .as_ref()
+            .ok_or(TicketError::SeatStatusPageRequired)?
+            .load_mut()?;
+        require!(
+            seat_status_page.seat_status_map == seat_status_map.key(),
+            TicketError::InvalidAccount
+        );
+        seat_status_map.update_seat_status(&mut seat_status_page, seat_index, SeatStatus::Redeemed)?;
+    }
+
     // 更新门票状态
     ticket.current_status = TicketStatus::Redeemed;
     ticket.redeemed_at = Some(current_time);
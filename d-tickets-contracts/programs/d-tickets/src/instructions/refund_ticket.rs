@@ -1,8 +1,14 @@
 use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Token, TokenAccount},
+    token_interface::{self, Burn, CloseAccount, Mint, TokenAccount as TicketTokenAccount, TokenInterface},
+};
 use crate::{
-    state::{TicketTypeAccount, TicketAccount, OrganizerEarnings, EventAccount},
+    state::{TicketTypeAccount, TicketAccount, TicketStatus, OrganizerEarnings, EventAccount, PlatformAccount, SeatStatusMap, SeatStatusPage, SeatStatus},
     error::TicketError,
     constants::*,
+    utils::{calculate_fee, Settlement},
 };
 
 #[derive(Accounts)]
@@ -35,22 +41,96 @@ pub struct RefundTicket<'info> {
     )]
     pub earnings: Account<'info, OrganizerEarnings>,
 
+    /// 活动资金托管账户（PDA，支付退款）
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref(), VAULT_SEED],
+        bump
+    )]
+    pub event_vault: SystemAccount<'info>,
+
+    /// 门票NFT的mint
+    #[account(
+        mut,
+        address = ticket.mint @ TicketError::InvalidAccount
+    )]
+    pub ticket_mint: InterfaceAccount<'info, Mint>,
+
+    /// 持有者的门票代币账户（退票时销毁并关闭）
+    #[account(
+        mut,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = refund_requester,
+        associated_token::token_program = ticket_token_program
+    )]
+    pub holder_token_account: InterfaceAccount<'info, TicketTokenAccount>,
+
     #[account(mut)]
     pub refund_requester: Signer<'info>,
+
+    /// 座位状态映射账户（对号入座门票必填，退票后标记座位为已退票）
+    #[account(mut)]
+    pub seat_status_map: Option<Account<'info, SeatStatusMap>>,
+
+    /// 座位所在的座位状态分页账户（对号入座门票必填）
+    #[account(mut)]
+    pub seat_status_page: Option<AccountLoader<'info, SeatStatusPage>>,
+
+    /// 持有者的支付代币账户（SPL代币结算时必填，接收退款）
+    #[account(mut)]
+    pub requester_payment_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 活动的SPL代币托管账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 支付结算使用的代币程序
+    pub token_program: Program<'info, Token>,
+    /// 门票NFT所属的代币程序（SPL Token 或 Token-2022）
+    pub ticket_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, PlatformAccount>,
 }
 
 pub fn handler(ctx: Context<RefundTicket>) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
     let ticket = &mut ctx.accounts.ticket;
     let ticket_type = &mut ctx.accounts.ticket_type;
     let event = &mut ctx.accounts.event;
     let earnings = &mut ctx.accounts.earnings;
     let current_time = Clock::get()?.unix_timestamp;
 
+    // 验证门票状态，防止重复退票
+    require!(
+        ticket.current_status != TicketStatus::Refunded,
+        TicketError::TicketAlreadyRefunded
+    );
+    require!(
+        ticket.current_status != TicketStatus::Redeemed,
+        TicketError::TicketAlreadyRedeemed
+    );
+    require!(
+        ticket.current_status == TicketStatus::Sold,
+        TicketError::InvalidTicketStatus
+    );
+
     // 验证退票权限
     require!(
         ticket.current_owner == ctx.accounts.refund_requester.key(),
         TicketError::NotTicketOwner
     );
+    require!(
+        ctx.accounts.holder_token_account.amount == 1,
+        TicketError::NotTicketOwner
+    );
 
     // 验证是否在退票时间范围内
     require!(
@@ -58,20 +138,103 @@ pub fn handler(ctx: Context<RefundTicket>) -> Result<()> {
         TicketError::RefundDeadlinePassed
     );
 
-    // 计算退款金额
-    let refund_amount = ticket.original_price;
+    // 升级前售出的门票未记录存入托管账户的金额，无法确定可退金额，不支持链上退票
+    require!(ticket.organizer_amount > 0, TicketError::RefundNotAllowed);
+
+    // 计算退款金额：以该门票实际存入托管账户的金额为上限（平台手续费不退），再扣除退票手续费
+    let refund_fee = calculate_fee(ticket.original_price, ticket_type.refund_fee_bps)?;
+    let refund_amount = ticket.organizer_amount.saturating_sub(refund_fee);
+
+    // 退款只能来自主办方尚未提取的收益
+    require!(
+        earnings.pending_amount >= refund_amount,
+        TicketError::InsufficientBalance
+    );
+
+    // 销毁门票NFT并关闭持有者的代币账户（租金退还给持有者）
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.ticket_token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.ticket_mint.to_account_info(),
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                authority: ctx.accounts.refund_requester.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    token_interface::close_account(CpiContext::new(
+        ctx.accounts.ticket_token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.holder_token_account.to_account_info(),
+            destination: ctx.accounts.refund_requester.to_account_info(),
+            authority: ctx.accounts.refund_requester.to_account_info(),
+        },
+    ))?;
+
+    // 从托管账户按结算币种退款给持有者
+    let settlement = Settlement::new(
+        earnings.payment_mint,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    );
+    let event_vault = settlement.party(
+        ctx.accounts.event_vault.to_account_info(),
+        ctx.accounts.vault_token_account.as_deref(),
+    )?;
+    let requester = settlement.party(
+        ctx.accounts.refund_requester.to_account_info(),
+        ctx.accounts.requester_payment_account.as_deref(),
+    )?;
+    settlement.transfer_from_vault(
+        &event_vault,
+        &requester,
+        &event_key,
+        ctx.bumps.event_vault,
+        refund_amount,
+    )?;
+
+    // 对号入座门票释放座位（标记为已退票，可再次出售）
+    if let Some(seat_index) = ticket.seat_index {
+        let seat_status_map = ctx.accounts.seat_status_map
+            .as_mut()
+            .ok_or(TicketError::InvalidAccount)?;
+        require!(
+            ticket.seat_status_map == Some(seat_status_map.key()),
+            TicketError::InvalidAccount
+        );
+        let mut seat_status_page = ctx.accounts.seat_status_page
+            .as_ref()
+            .ok_or(TicketError::SeatStatusPageRequired)?
+            .load_mut()?;
+        require!(
+            seat_status_page.seat_status_map == seat_status_map.key(),
+            TicketError::InvalidAccount
+        );
+        seat_status_map.update_seat_status(&mut seat_status_page, seat_index, SeatStatus::Refunded)?;
+    }
+
+    // 更新门票状态
+    ticket.current_status = TicketStatus::Refunded;
 
     // 更新统计数据
     ticket_type.refunded_count += 1;
     ticket_type.sold_count = ticket_type.sold_count.saturating_sub(1);
     event.total_tickets_refunded += 1;
     event.total_tickets_sold = event.total_tickets_sold.saturating_sub(1);
-    event.total_revenue = event.total_revenue.saturating_sub(refund_amount);
+    event.total_revenue = event.total_revenue.saturating_sub(ticket.original_price);
     earnings.total_earnings = earnings.total_earnings.saturating_sub(refund_amount);
-    earnings.pending_amount = earnings.pending_amount.saturating_sub(refund_amount);
+    earnings.pending_amount -= refund_amount;
 
     // 更新时间戳
     event.updated_at = current_time;
 
+    msg!(
+        "退票成功: 门票mint: {}, 退款金额: {}, 手续费: {}",
+        ticket.mint,
+        refund_amount,
+        refund_fee
+    );
+
     Ok(())
-} 
\ No newline at end of file
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{SeatStatusMap, SeatStatusPage, SeatStatus, SeatHold},
+    error::TicketError,
+    constants::*,
+};
+
+/// 释放选座锁定：锁定过期后任何人均可调用，买家本人可随时提前释放
+#[derive(Accounts)]
+pub struct ReleaseExpiredHolds<'info> {
+    #[account(
+        mut,
+        address = seat_hold.seat_status_map @ TicketError::InvalidAccount
+    )]
+    pub seat_status_map: Box<Account<'info, SeatStatusMap>>,
+
+    /// 座位所在的座位状态分页账户（同一次操作的座位需位于同一分页）
+    #[account(
+        mut,
+        constraint = seat_status_page.load()?.seat_status_map == seat_status_map.key() @ TicketError::InvalidAccount
+    )]
+    pub seat_status_page: AccountLoader<'info, SeatStatusPage>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [SEAT_HOLD_SEED, seat_status_map.key().as_ref(), seat_hold.buyer.as_ref()],
+        bump = seat_hold.bump
+    )]
+    pub seat_hold: Box<Account<'info, SeatHold>>,
+
+    /// CHECK: 锁定的买家，接收返还的租金
+    #[account(
+        mut,
+        address = seat_hold.buyer @ TicketError::InvalidAccount
+    )]
+    pub buyer: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReleaseExpiredHolds>) -> Result<()> {
+    let seat_status_map = &mut ctx.accounts.seat_status_map;
+    let seat_hold = &ctx.accounts.seat_hold;
+    let current_time = Clock::get()?.unix_timestamp;
+    let mut seat_status_page = ctx.accounts.seat_status_page.load_mut()?;
+
+    require!(
+        seat_hold.is_expired(current_time) || ctx.accounts.caller.key() == seat_hold.buyer,
+        TicketError::SeatHoldNotExpired
+    );
+
+    // 仍处于锁定状态的座位恢复为锁定前的状态（已被主办方改为其他状态的座位保持不变）
+    for (seat_index, prior_status) in seat_hold.seat_indices.iter().zip(seat_hold.prior_statuses.iter()) {
+        let status = seat_status_map.get_seat_status(&seat_status_page, *seat_index)?;
+        if status == SeatStatus::TempLocked {
+            seat_status_map.update_seat_status(
+                &mut seat_status_page,
+                *seat_index,
+                prior_status.clone(),
+            )?;
+        }
+    }
+
+    msg!(
+        "座位锁定已释放: 买家: {}, 释放座位数: {}",
+        seat_hold.buyer,
+        seat_hold.seat_indices.len()
+    );
+
+    // 锁定账户通过 close = buyer 约束关闭并返还租金
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{EventAccount, OrganizerEarnings, PlatformAccount},
+    error::TicketError,
+    constants::*,
+};
+
+#[derive(Accounts)]
+pub struct SetPayoutWallet<'info> {
+    #[account(
+        seeds = [EVENT_SEED, organizer.key().as_ref(), event.event_name.as_bytes()],
+        bump = event.bump,
+        has_one = organizer @ TicketError::NotEventOrganizer
+    )]
+    pub event: Account<'info, EventAccount>,
+
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref()],
+        bump = earnings.bump
+    )]
+    pub earnings: Account<'info, OrganizerEarnings>,
+
+    pub organizer: Signer<'info>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, PlatformAccount>,
+}
+
+pub fn handler(
+    ctx: Context<SetPayoutWallet>,
+    payout_wallet: Option<Pubkey>,
+) -> Result<()> {
+    let earnings = &mut ctx.accounts.earnings;
+
+    earnings.payout_wallet = payout_wallet;
+
+    msg!(
+        "收款钱包更新成功: 活动: {}, 收款钱包: {:?}",
+        ctx.accounts.event.event_name,
+        payout_wallet
+    );
+
+    Ok(())
+}
@@ -1,12 +1,12 @@
 use anchor_lang::prelude::*;
-use crate::{constants::*, error::TicketError, state::*};
+use crate::{constants::*, error::TicketError, state::*, utils::fund_rent_exemption};
 
 #[derive(Accounts)]
 pub struct SetupPlatform<'info> {
     #[account(
         init_if_needed,
         payer = admin,
-        space = PlatformAccount::INIT_SPACE,
+        space = 8 + PlatformAccount::INIT_SPACE,
         seeds = [PLATFORM_SEED],
         bump
     )]
@@ -16,6 +16,9 @@ pub struct SetupPlatform<'info> {
     pub admin: Signer<'info>,
     
     /// CHECK: 收款账户，可以是任何有效的钱包地址。如果为None，将使用默认地址或保持现有地址
+    /// 通过 new_fee_recipient 设置收款地址时必须传入该账户，由管理员预存其免租余额；
+    /// 更新时仅传入该账户不会更改收款地址
+    #[account(mut)]
     pub fee_recipient: Option<AccountInfo<'info>>,
     
     pub system_program: Program<'info, System>,
@@ -55,6 +58,16 @@ pub fn handler(
             DEFAULT_FEE_RECIPIENT.parse::<Pubkey>().unwrap()
         };
 
+        // 默认收款地址无需传入收款账户；指定收款地址时预存其免租余额
+        if new_fee_recipient.is_some() || ctx.accounts.fee_recipient.is_some() {
+            fund_fee_recipient(
+                &ctx.accounts.fee_recipient,
+                &ctx.accounts.admin,
+                &ctx.accounts.system_program,
+                fee_recipient,
+            )?;
+        }
+
         // 初始化平台账户
         platform.admin = ctx.accounts.admin.key();
         platform.platform_fee_bps = default_fee_bps;
@@ -86,13 +99,16 @@ pub fn handler(
             TicketError::Unauthorized
         );
 
-        // 更新收款地址
+        // 更新收款地址（仅在显式传入 new_fee_recipient 时更改）
         if let Some(recipient) = new_fee_recipient {
+            fund_fee_recipient(
+                &ctx.accounts.fee_recipient,
+                &ctx.accounts.admin,
+                &ctx.accounts.system_program,
+                recipient,
+            )?;
             platform.fee_recipient = recipient;
             msg!("Fee recipient updated to: {}", recipient);
-        } else if let Some(recipient_account) = &ctx.accounts.fee_recipient {
-            platform.fee_recipient = recipient_account.key();
-            msg!("Fee recipient updated to: {}", recipient_account.key());
         }
 
         // 更新平台手续费
@@ -120,4 +136,20 @@ pub fn handler(
     }
 
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// 预存收款账户的免租余额，避免首笔低于租金的平台手续费转账失败
+fn fund_fee_recipient<'info>(
+    fee_recipient: &Option<AccountInfo<'info>>,
+    admin: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    recipient: Pubkey,
+) -> Result<()> {
+    let recipient_account = fee_recipient.as_ref().ok_or(TicketError::InvalidAccount)?;
+    require_keys_eq!(recipient_account.key(), recipient, TicketError::InvalidAccount);
+    fund_rent_exemption(
+        admin.to_account_info(),
+        recipient_account.clone(),
+        system_program.to_account_info(),
+    )
+}
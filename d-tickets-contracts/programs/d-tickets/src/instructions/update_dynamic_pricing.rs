@@ -24,7 +24,8 @@ pub struct UpdateDynamicPricing<'info> {
 
     #[account(
         seeds = [PLATFORM_SEED],
-        bump = platform.bump
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
     )]
     pub platform: Account<'info, PlatformAccount>,
 }
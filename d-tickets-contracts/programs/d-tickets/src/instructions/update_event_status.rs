@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::{
-    state::{EventAccount, EventStatus, VenueAccount, VenueStatus},
+    state::{EventAccount, EventStatus, VenueAccount, VenueStatus, PlatformAccount},
     error::TicketError,
     constants::*,
 };
@@ -22,6 +22,14 @@ pub struct UpdateEventStatus<'info> {
 
     #[account(mut)]
     pub organizer: Signer<'info>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, PlatformAccount>,
 }
 
 pub fn handler(
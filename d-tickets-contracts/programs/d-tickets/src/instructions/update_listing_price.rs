@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{MarketplaceListingAccount, TicketAccount, TicketTypeAccount, PlatformAccount, ListingStatus},
+    error::TicketError,
+    constants::*,
+};
+
+/// 挂单价格更新事件（供索引器更新订单簿）
+#[event]
+pub struct ListingPriceUpdatedEvent {
+    /// 挂单PDA
+    pub listing: Pubkey,
+    /// 门票mint
+    pub ticket_mint: Pubkey,
+    /// 卖家地址
+    pub seller: Pubkey,
+    /// 原挂单价格
+    pub old_price: u64,
+    /// 新挂单价格
+    pub new_price: u64,
+    /// 更新时间戳
+    pub updated_at: i64,
+}
+
+#[derive(Accounts)]
+pub struct UpdateListingPrice<'info> {
+    #[account(
+        mut,
+        seeds = [MARKETPLACE_LISTING_SEED, listing.ticket_mint.as_ref()],
+        bump = listing.bump,
+        constraint = listing.status == ListingStatus::Active @ TicketError::ListingNotFound,
+        constraint = listing.seller == seller.key() @ TicketError::NotTicketOwner
+    )]
+    pub listing: Account<'info, MarketplaceListingAccount>,
+
+    #[account(
+        seeds = [TICKET_SEED, ticket.mint.as_ref()],
+        bump = ticket.bump,
+        constraint = ticket.mint == listing.ticket_mint @ TicketError::InvalidAccount
+    )]
+    pub ticket: Account<'info, TicketAccount>,
+
+    #[account(
+        seeds = [TICKET_TYPE_SEED, ticket.event.as_ref(), ticket.ticket_type_name.as_bytes()],
+        bump = ticket_type.bump
+    )]
+    pub ticket_type: Account<'info, TicketTypeAccount>,
+
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, PlatformAccount>,
+
+    pub seller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateListingPrice>, new_price: u64) -> Result<()> {
+    let listing = &mut ctx.accounts.listing;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // 验证挂单未过期，新价格在主办方设定的转售价格范围内
+    require!(
+        !listing.is_expired(current_time),
+        TicketError::ListingExpired
+    );
+    require!(new_price > 0, TicketError::InvalidPrice);
+    ctx.accounts.ticket_type.validate_resale_price(ctx.accounts.ticket.original_price, new_price)?;
+
+    // 更新挂单价格（费率仍按挂单时锁定的比例计算）
+    let old_price = listing.price;
+    listing.price = new_price;
+
+    emit!(ListingPriceUpdatedEvent {
+        listing: listing.key(),
+        ticket_mint: listing.ticket_mint,
+        seller: listing.seller,
+        old_price,
+        new_price,
+        updated_at: current_time,
+    });
+
+    msg!(
+        "挂单价格更新成功: 门票mint: {}, 价格: {} -> {}",
+        listing.ticket_mint,
+        old_price,
+        new_price
+    );
+
+    Ok(())
+}
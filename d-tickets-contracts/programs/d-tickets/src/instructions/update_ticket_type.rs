@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::{
-    state::{EventAccount, TicketTypeAccount, PlatformAccount},
+    state::{EventAccount, TicketTypeAccount, TicketTypeRulesUpdate, PlatformAccount},
     error::TicketError,
     constants::*,
 };
@@ -25,7 +25,8 @@ pub struct UpdateTicketType<'info> {
 
     #[account(
         seeds = [PLATFORM_SEED],
-        bump = platform.bump
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
     )]
     pub platform: Account<'info, PlatformAccount>,
 
@@ -39,6 +40,7 @@ pub fn handler(
     new_price: Option<u64>,
     new_max_resale_royalty: Option<u16>,
     new_dynamic_pricing_rules_hash: Option<String>,
+    new_rules: TicketTypeRulesUpdate,
 ) -> Result<()> {
     let ticket_type = &mut ctx.accounts.ticket_type;
     let platform = &ctx.accounts.platform;
@@ -76,5 +78,33 @@ pub fn handler(
         ticket_type.dynamic_pricing_rules_hash = Some(rules_hash);
     }
 
+    // 更新退票手续费比例
+    if let Some(refund_fee_bps) = new_rules.refund_fee_bps {
+        require!(refund_fee_bps <= MAX_REFUND_FEE_BPS, TicketError::InvalidArgument);
+        ticket_type.refund_fee_bps = refund_fee_bps;
+    }
+
+    // 更新每钱包限购数量（0 表示不限制）
+    if let Some(max_per_wallet) = new_rules.max_per_wallet {
+        require!(
+            max_per_wallet <= ticket_type.total_supply,
+            TicketError::InvalidArgument
+        );
+        ticket_type.max_per_wallet = max_per_wallet;
+    }
+
+    // 更新转售价格上下限（0 表示不限制），上限不能低于下限
+    if let Some(max_resale_markup_bps) = new_rules.max_resale_markup_bps {
+        ticket_type.max_resale_markup_bps = max_resale_markup_bps;
+    }
+    if let Some(min_resale_price_bps) = new_rules.min_resale_price_bps {
+        ticket_type.min_resale_price_bps = min_resale_price_bps;
+    }
+    require!(
+        ticket_type.max_resale_markup_bps == 0
+            || ticket_type.max_resale_markup_bps >= ticket_type.min_resale_price_bps,
+        TicketError::InvalidArgument
+    );
+
     Ok(())
 } 
\ No newline at end of file
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{VenueAccount, VenueType, VenueStatus};
+use crate::state::{VenueAccount, VenueType, VenueStatus, PlatformAccount};
+use crate::constants::PLATFORM_SEED;
 use crate::error::TicketError;
 
 #[derive(Accounts)]
@@ -20,6 +21,14 @@ pub struct UpdateVenue<'info> {
 
     /// 系统程序
     pub system_program: Program<'info, System>,
+
+    /// 平台账户（平台暂停时拒绝执行）
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform.bump,
+        constraint = !platform.is_paused @ TicketError::PlatformPaused
+    )]
+    pub platform: Account<'info, PlatformAccount>,
 }
 
 pub fn handler(
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::{
+    state::MarketplaceOfferAccount,
+    error::TicketError,
+    constants::*,
+    utils::Settlement,
+};
+
+/// 撤回出价：买家可随时撤回，出价过期后任何人均可关闭，托管金额和租金退还买家
+#[derive(Accounts)]
+pub struct WithdrawOffer<'info> {
+    #[account(
+        mut,
+        seeds = [MARKETPLACE_OFFER_SEED, offer.buyer.as_ref(), offer.ticket_type.as_ref(), &offer.offer_id.to_le_bytes()],
+        bump = offer.bump,
+        close = buyer
+    )]
+    pub offer: Account<'info, MarketplaceOfferAccount>,
+
+    /// 出价托管代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// 买家的收款代币账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub buyer_payment_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: 出价买家，地址必须与出价一致，接收退还的托管金额和租金
+    #[account(
+        mut,
+        address = offer.buyer @ TicketError::InvalidAccount
+    )]
+    pub buyer: UncheckedAccount<'info>,
+
+    /// 调用者（买家本人，或出价过期后的任何人）
+    pub caller: Signer<'info>,
+
+    /// 支付结算使用的代币程序
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<WithdrawOffer>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // 买家本人可随时撤回，其他人只能关闭已过期的出价
+    require!(
+        ctx.accounts.caller.key() == offer.buyer || offer.is_expired(current_time),
+        TicketError::OfferNotExpired
+    );
+
+    // 托管金额退还买家，并关闭托管代币账户
+    let settlement = Settlement::new(
+        offer.payment_mint,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    );
+    let escrow = settlement.party(
+        offer.to_account_info(),
+        ctx.accounts.escrow_token_account.as_ref(),
+    )?;
+    let buyer = settlement.party(
+        ctx.accounts.buyer.to_account_info(),
+        ctx.accounts.buyer_payment_account.as_ref(),
+    )?;
+    let offer_id_bytes = offer.offer_id.to_le_bytes();
+    let offer_seeds: &[&[u8]] = &[
+        MARKETPLACE_OFFER_SEED,
+        offer.buyer.as_ref(),
+        offer.ticket_type.as_ref(),
+        &offer_id_bytes,
+        &[offer.bump],
+    ];
+    settlement.transfer_from_escrow(&escrow, &buyer, offer_seeds, offer.price)?;
+    settlement.close_escrow(&escrow, &buyer, ctx.accounts.buyer.to_account_info(), offer_seeds)?;
+
+    msg!(
+        "出价撤回成功: 买家: {}, 退还金额: {}",
+        offer.buyer,
+        offer.price
+    );
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
 use crate::{
     state::{EventAccount, OrganizerEarnings},
     error::TicketError,
     constants::*,
+    utils::{transfer_lamports, Settlement},
 };
 
 #[derive(Accounts)]
@@ -22,14 +24,41 @@ pub struct WithdrawProceeds<'info> {
     )]
     pub earnings: Account<'info, OrganizerEarnings>,
 
+    /// 活动资金托管账户（PDA）
+    #[account(
+        mut,
+        seeds = [EARNINGS_SEED, event.key().as_ref(), VAULT_SEED],
+        bump
+    )]
+    pub event_vault: SystemAccount<'info>,
+
+    /// CHECK: 收款账户，必须是登记的收款钱包（未登记时为主办方钱包）
+    #[account(
+        mut,
+        constraint = payout_destination.key() == earnings.payout_wallet.unwrap_or(organizer.key()) @ TicketError::InvalidAccount
+    )]
+    pub payout_destination: UncheckedAccount<'info>,
+
+    /// 收款代币账户（SPL代币结算时必填，持有者须为收款账户）
+    #[account(mut)]
+    pub payout_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// 活动的SPL代币托管账户（SPL代币结算时必填）
+    #[account(mut)]
+    pub vault_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
     #[account(mut)]
     pub organizer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(
     ctx: Context<WithdrawProceeds>,
     amount: u64,
 ) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
     let earnings = &mut ctx.accounts.earnings;
     let current_time = Clock::get()?.unix_timestamp;
 
@@ -40,6 +69,43 @@ pub fn handler(
         TicketError::WithdrawAmountTooLarge
     );
 
+    // 早期创建的SOL托管账户未预存免租余额，由主办方补足，保证收益可以全额提取
+    if earnings.payment_mint.is_none() {
+        let rent_reserve = Rent::get()?.minimum_balance(0);
+        let reserve_shortfall = rent_reserve
+            .saturating_add(amount)
+            .saturating_sub(ctx.accounts.event_vault.lamports())
+            .min(rent_reserve);
+        transfer_lamports(
+            ctx.accounts.organizer.to_account_info(),
+            ctx.accounts.event_vault.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            reserve_shortfall,
+        )?;
+    }
+
+    // 从托管账户按结算币种转出到收款账户
+    let settlement = Settlement::new(
+        earnings.payment_mint,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    );
+    let event_vault = settlement.party(
+        ctx.accounts.event_vault.to_account_info(),
+        ctx.accounts.vault_token_account.as_deref(),
+    )?;
+    let payout_destination = settlement.party(
+        ctx.accounts.payout_destination.to_account_info(),
+        ctx.accounts.payout_token_account.as_deref(),
+    )?;
+    settlement.transfer_from_vault(
+        &event_vault,
+        &payout_destination,
+        &event_key,
+        ctx.bumps.event_vault,
+        amount,
+    )?;
+
     // 更新收益记录
     earnings.pending_amount -= amount;
     earnings.withdrawn_amount += amount;
@@ -47,10 +113,11 @@ pub fn handler(
     earnings.last_withdrawal_at = Some(current_time);
 
     msg!(
-        "收益提取成功: 主办方: {}, 金额: {} lamports",
+        "收益提取成功: 主办方: {}, 收款账户: {}, 金额: {}",
         ctx.accounts.organizer.key(),
+        ctx.accounts.payout_destination.key(),
         amount
     );
 
     Ok(())
-} 
\ No newline at end of file
+}
@@ -2,6 +2,10 @@ pub mod constants;
 pub mod error;
 pub mod instructions;
 pub mod state;
+pub mod utils;
+
+#[cfg(test)]
+mod test_fixtures;
 
 use anchor_lang::prelude::*;
 use instructions::*;
@@ -99,6 +103,16 @@ pub mod d_tickets {
         )
     }
 
+    /// 批量配置座位（座位账户通过 remaining_accounts 传入）
+    pub fn configure_seats_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, ConfigureSeatsBatch<'info>>,
+        venue_name: String,
+        ticket_type_name: String,
+        seats: Vec<SeatData>,
+    ) -> Result<()> {
+        instructions::configure_seats::configure_seats_batch(ctx, venue_name, ticket_type_name, seats)
+    }
+
     /// 创建或更新座位状态映射（支持添加和修改）
     pub fn create_seat_status_map(
         ctx: Context<CreateOrUpdateSeatStatusMap>,
@@ -119,8 +133,8 @@ pub mod d_tickets {
     }
 
     /// 批量更新座位状态
-    pub fn batch_update_seat_status(
-        ctx: Context<BatchUpdateSeatStatus>,
+    pub fn batch_update_seat_status<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchUpdateSeatStatus<'info>>,
         ticket_type_name: String,
         area_id: String,
         seat_updates: Vec<SeatStatusUpdate>,
@@ -133,9 +147,49 @@ pub mod d_tickets {
         )
     }
 
+    /// 创建座位状态分页账户（每页16000个座位）
+    pub fn create_seat_status_page(
+        ctx: Context<CreateSeatStatusPage>,
+        ticket_type_name: String,
+        area_id: String,
+        page_index: u16,
+    ) -> Result<()> {
+        instructions::configure_seats::create_seat_status_page(ctx, ticket_type_name, area_id, page_index)
+    }
+
+    /// 登记或追加座位布局（链上推导排号和座位号）
+    pub fn update_seat_layout(
+        ctx: Context<UpdateSeatLayout>,
+        ticket_type_name: String,
+        area_id: String,
+        rows: Vec<SeatRow>,
+        reset: bool,
+    ) -> Result<()> {
+        instructions::configure_seats::update_seat_layout(ctx, ticket_type_name, area_id, rows, reset)
+    }
+
+    /// 设置座位价格档位（同一区域内按座位索引区间差异化定价）
+    pub fn set_seat_price_tiers(
+        ctx: Context<SetSeatPriceTiers>,
+        ticket_type_name: String,
+        area_id: String,
+        tiers: Vec<SeatPriceTier>,
+    ) -> Result<()> {
+        instructions::configure_seats::set_seat_price_tiers(ctx, ticket_type_name, area_id, tiers)
+    }
+
+    /// 迁移座位状态映射至新的位图编码
+    pub fn migrate_seat_status_map(
+        ctx: Context<MigrateSeatStatusMap>,
+        ticket_type_name: String,
+        area_id: String,
+    ) -> Result<()> {
+        instructions::configure_seats::migrate_seat_status_map(ctx, ticket_type_name, area_id)
+    }
+
     /// 查询座位状态
     pub fn get_seat_status_batch(
-        ctx: Context<BatchUpdateSeatStatus>,
+        ctx: Context<GetSeatStatusBatch>,
         ticket_type_name: String,
         area_id: String,
         seat_indices: Vec<u32>,
@@ -148,9 +202,9 @@ pub mod d_tickets {
         )
     }
 
-    /// 删除座位状态映射
-    pub fn delete_seat_status_map(
-        ctx: Context<DeleteSeatStatusMap>,
+    /// 删除座位状态映射（同时关闭分页、座位布局和价格档位账户）
+    pub fn delete_seat_status_map<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DeleteSeatStatusMap<'info>>,
         ticket_type_name: String,
         area_id: String,
     ) -> Result<()> {
@@ -161,26 +215,36 @@ pub mod d_tickets {
         )
     }
 
-    /// 更新座位状态
+    /// 将座位账户关联到座位状态映射中的座位索引（关联后座位状态以位图为准）
+    pub fn link_seat_account(
+        ctx: Context<LinkSeatAccount>,
+        venue_name: String,
+        ticket_type_name: String,
+        seat_number: String,
+        seat_index: u32,
+    ) -> Result<()> {
+        instructions::configure_seats::link_seat_account(ctx, venue_name, ticket_type_name, seat_number, seat_index)
+    }
+
+    /// 校验并修复座位账户与座位状态映射的一致性
+    pub fn sync_seat_account(ctx: Context<SyncSeatAccount>) -> Result<()> {
+        instructions::configure_seats::sync_seat_account(ctx)
+    }
+
+    /// 更新座位状态（仅限未关联座位状态映射的座位账户）
     pub fn update_seat_status(
         ctx: Context<UpdateSeatStatus>,
         venue_name: String,
         ticket_type_name: String,
         seat_number: String,
-        new_status: SeatStatus,
-        event_key: Option<Pubkey>,
-        ticket_nft: Option<Pubkey>,
-        ticket_type_id: Option<u8>,
+        update: SeatAccountUpdate,
     ) -> Result<()> {
         instructions::configure_seats::update_seat_status(
             ctx,
             venue_name,
             ticket_type_name,
             seat_number,
-            new_status,
-            event_key,
-            ticket_nft,
-            ticket_type_id,
+            update,
         )
     }
 
@@ -250,6 +314,7 @@ pub mod d_tickets {
         new_price: Option<u64>,
         new_max_resale_royalty: Option<u16>,
         new_dynamic_pricing_rules_hash: Option<String>,
+        new_rules: TicketTypeRulesUpdate,
     ) -> Result<()> {
         instructions::update_ticket_type::handler(
             ctx,
@@ -257,6 +322,7 @@ pub mod d_tickets {
             new_price,
             new_max_resale_royalty,
             new_dynamic_pricing_rules_hash,
+            new_rules,
         )
     }
 
@@ -334,6 +400,49 @@ pub mod d_tickets {
         instructions::purchase_ticket::handler(ctx, ticket_type_name)
     }
 
+    /// 购买门票（Token-2022 门票NFT，元数据写入mint并启用转账钩子）
+    pub fn purchase_ticket_token_2022(
+        ctx: Context<PurchaseTicketToken2022>,
+        ticket_type_name: String,
+    ) -> Result<()> {
+        instructions::purchase_ticket_token_2022::handler(ctx, ticket_type_name)
+    }
+
+    /// 一次购买多张门票（受单次购买上限和每钱包限购约束）
+    pub fn purchase_tickets<'info>(
+        ctx: Context<'_, '_, '_, 'info, PurchaseTickets<'info>>,
+        ticket_type_name: String,
+        quantity: u32,
+    ) -> Result<()> {
+        instructions::purchase_tickets::handler(ctx, ticket_type_name, quantity)
+    }
+
+    /// 对号入座购票（原子地占用座位状态映射中的座位）
+    pub fn purchase_seat(
+        ctx: Context<PurchaseSeat>,
+        ticket_type_name: String,
+        area_id: String,
+        seat_index: u32,
+    ) -> Result<()> {
+        instructions::purchase_seat::handler(ctx, ticket_type_name, area_id, seat_index)
+    }
+
+    /// 选座锁定（结算期间临时锁定座位，默认5分钟）
+    pub fn hold_seats(
+        ctx: Context<HoldSeats>,
+        ticket_type_name: String,
+        area_id: String,
+        seat_indices: Vec<u32>,
+        hold_duration: Option<i64>,
+    ) -> Result<()> {
+        instructions::hold_seats::handler(ctx, ticket_type_name, area_id, seat_indices, hold_duration)
+    }
+
+    /// 释放过期的选座锁定（任何人可调用）
+    pub fn release_expired_holds(ctx: Context<ReleaseExpiredHolds>) -> Result<()> {
+        instructions::release_expired_holds::handler(ctx)
+    }
+
     /// 退票
     pub fn refund_ticket(
         ctx: Context<RefundTicket>,
@@ -341,29 +450,84 @@ pub mod d_tickets {
         instructions::refund_ticket::handler(ctx)
     }
 
+    /// 授权二级市场之外的门票转让（Token-2022 门票，预付版税）
+    pub fn approve_ticket_transfer(
+        ctx: Context<ApproveTicketTransfer>,
+        recipient: Pubkey,
+        declared_price: u64,
+    ) -> Result<()> {
+        instructions::approve_ticket_transfer::handler(ctx, recipient, declared_price)
+    }
+
+    /// 完成门票转让（仅由转账钩子程序回调）
+    pub fn complete_ticket_transfer(
+        ctx: Context<CompleteTicketTransfer>,
+    ) -> Result<()> {
+        instructions::complete_ticket_transfer::handler(ctx)
+    }
+
     // ===== 二级市场功能 =====
     /// 上架门票到二级市场
-    pub fn list_ticket_for_sale(
-        ctx: Context<ListTicketForSale>,
+    pub fn list_ticket_for_sale<'info>(
+        ctx: Context<'_, '_, '_, 'info, ListTicketForSale<'info>>,
         price: u64,
+        expires_at: Option<i64>,
     ) -> Result<()> {
-        instructions::list_ticket_for_sale::handler(ctx, price)
+        instructions::list_ticket_for_sale::handler(ctx, price, expires_at)
     }
 
     /// 从二级市场购买门票
-    pub fn buy_ticket_from_market(
-        ctx: Context<BuyTicketFromMarket>,
+    pub fn buy_ticket_from_market<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyTicketFromMarket<'info>>,
     ) -> Result<()> {
         instructions::buy_ticket_from_market::handler(ctx)
     }
 
     /// 取消二级市场挂单
-    pub fn cancel_ticket_listing(
-        ctx: Context<CancelTicketListing>,
+    pub fn cancel_ticket_listing<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelTicketListing<'info>>,
     ) -> Result<()> {
         instructions::cancel_ticket_listing::handler(ctx)
     }
 
+    /// 更新二级市场挂单价格（受转售价格上下限约束）
+    pub fn update_listing_price(
+        ctx: Context<UpdateListingPrice>,
+        new_price: u64,
+    ) -> Result<()> {
+        instructions::update_listing_price::handler(ctx, new_price)
+    }
+
+    /// 关闭已过期的挂单（任何人均可调用，门票和租金退还卖家）
+    pub fn close_expired_listing<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseExpiredListing<'info>>,
+    ) -> Result<()> {
+        instructions::close_expired_listing::handler(ctx)
+    }
+
+    /// 求购出价（出价金额托管在出价PDA中，可指定门票或票种下任意门票）
+    pub fn make_offer(
+        ctx: Context<MakeOffer>,
+        offer_id: u64,
+        ticket_mint: Option<Pubkey>,
+        price: u64,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::make_offer::handler(ctx, offer_id, ticket_mint, price, expires_at)
+    }
+
+    /// 门票持有者接受出价
+    pub fn accept_offer<'info>(
+        ctx: Context<'_, '_, '_, 'info, AcceptOffer<'info>>,
+    ) -> Result<()> {
+        instructions::accept_offer::handler(ctx)
+    }
+
+    /// 撤回出价（买家随时可撤回，过期后任何人均可关闭）
+    pub fn withdraw_offer(ctx: Context<WithdrawOffer>) -> Result<()> {
+        instructions::withdraw_offer::handler(ctx)
+    }
+
     // ===== 入场核销功能 =====
     /// 核销门票（入场验证）
     pub fn redeem_ticket(
@@ -383,6 +547,21 @@ pub mod d_tickets {
         instructions::setup_platform::handler(ctx, platform_fee_bps, new_fee_recipient, new_is_paused)
     }
 
+    /// 添加支持的支付代币（平台管理员）
+    pub fn add_supported_token(
+        ctx: Context<AddSupportedToken>,
+    ) -> Result<()> {
+        instructions::manage_supported_tokens::add_supported_token(ctx)
+    }
+
+    /// 移除支持的支付代币（平台管理员）
+    pub fn remove_supported_token(
+        ctx: Context<RemoveSupportedToken>,
+        token_mint: Pubkey,
+    ) -> Result<()> {
+        instructions::manage_supported_tokens::remove_supported_token(ctx, token_mint)
+    }
+
     /// 提取收益（主办方）
     pub fn withdraw_proceeds(
         ctx: Context<WithdrawProceeds>,
@@ -390,4 +569,17 @@ pub mod d_tickets {
     ) -> Result<()> {
         instructions::withdraw_proceeds::handler(ctx, amount)
     }
+
+    /// 设置收益收款钱包（主办方）
+    pub fn set_payout_wallet(
+        ctx: Context<SetPayoutWallet>,
+        payout_wallet: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_payout_wallet::handler(ctx, payout_wallet)
+    }
+
+    /// 迁移旧版账户布局（按当前结构扩容，新增字段读取为默认值）
+    pub fn migrate_account_layout(ctx: Context<MigrateAccountLayout>) -> Result<()> {
+        instructions::migrate_account_layout::handler(ctx)
+    }
 }
\ No newline at end of file
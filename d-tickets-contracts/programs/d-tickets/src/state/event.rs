@@ -162,7 +162,7 @@ pub struct TicketTypeAccount {
     pub type_name: String,
     /// 初始价格
     pub initial_price: u64,
-    /// 当前价格（动态定价会更新）
+    /// 当前价格（动态定价会更新，以结算币种的最小单位计）
     pub current_price: u64,
     /// 总发行数量
     pub total_supply: u32,
@@ -181,6 +181,62 @@ pub struct TicketTypeAccount {
     pub last_price_update: i64,
     /// PDA bump值（用于性能优化）
     pub bump: u8,
+    /// 退票时不予退还的手续费比例（基点）
+    ///
+    /// 以下字段追加在原有字段之后，升级前创建的账户需先调用 migrate_account_layout 扩容
+    pub refund_fee_bps: u16,
+    /// 定价代币（None 为 SOL，否则为SPL代币mint）
+    pub payment_mint: Option<Pubkey>,
+    /// 每个钱包最多可购买的数量（0 表示不限制）
+    pub max_per_wallet: u32,
+    /// 转售价格上限，相对门票原价的比例（基点，如12000为原价的120%，0 表示不限制）
+    pub max_resale_markup_bps: u16,
+    /// 转售价格下限，相对门票原价的比例（基点，0 表示不限制）
+    pub min_resale_price_bps: u16,
+}
+
+impl TicketTypeAccount {
+    /// 下一张门票mint的序号（已售+已退，只增不减，避免退票后PDA冲突）
+    pub fn next_mint_index(&self) -> u32 {
+        self.sold_count + self.refunded_count
+    }
+
+    /// 验证转售价格在主办方设定的上下限之内（按门票原价计算）
+    pub fn validate_resale_price(&self, original_price: u64, price: u64) -> Result<()> {
+        let bound = |bps: u16| -> Result<u128> {
+            Ok((original_price as u128)
+                .checked_mul(bps as u128)
+                .ok_or(crate::error::TicketError::Overflow)?
+                / crate::constants::BASIS_POINTS_DIVISOR as u128)
+        };
+
+        if self.max_resale_markup_bps > 0 {
+            require!(
+                price as u128 <= bound(self.max_resale_markup_bps)?,
+                crate::error::TicketError::ResalePriceOutOfBounds
+            );
+        }
+        if self.min_resale_price_bps > 0 {
+            require!(
+                price as u128 >= bound(self.min_resale_price_bps)?,
+                crate::error::TicketError::ResalePriceOutOfBounds
+            );
+        }
+        Ok(())
+    }
+}
+
+/// 票种购买及转售规则更新参数（字段为空时保持不变）
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct TicketTypeRulesUpdate {
+    /// 退票时不予退还的手续费比例（基点）
+    pub refund_fee_bps: Option<u16>,
+    /// 每个钱包最多可购买的数量（0 表示不限制）
+    pub max_per_wallet: Option<u32>,
+    /// 转售价格上限，相对门票原价的比例（基点，0 表示不限制）
+    pub max_resale_markup_bps: Option<u16>,
+    /// 转售价格下限，相对门票原价的比例（基点，0 表示不限制）
+    pub min_resale_price_bps: Option<u16>,
 }
 
 /// 活动状态枚举
@@ -229,4 +285,47 @@ pub enum PricingTriggerType {
     TimeBeforeEvent,
     /// 基于剩余时间（距离销售结束的秒数）
     TimeBeforeSaleEnd,
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use crate::error::TicketError;
+    use crate::test_fixtures::ticket_type;
+
+    #[test]
+    fn validate_resale_price_without_bounds_accepts_any_price() {
+        let ticket_type = ticket_type(0, 0);
+        assert!(ticket_type.validate_resale_price(1_000, 0).is_ok());
+        assert!(ticket_type.validate_resale_price(1_000, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn validate_resale_price_enforces_inclusive_bounds() {
+        // 上限为原价的120%，下限为原价的80%
+        let ticket_type = ticket_type(12_000, 8_000);
+        assert!(ticket_type.validate_resale_price(1_000, 800).is_ok());
+        assert!(ticket_type.validate_resale_price(1_000, 1_200).is_ok());
+        assert_eq!(
+            ticket_type.validate_resale_price(1_000, 799).unwrap_err(),
+            TicketError::ResalePriceOutOfBounds.into()
+        );
+        assert_eq!(
+            ticket_type.validate_resale_price(1_000, 1_201).unwrap_err(),
+            TicketError::ResalePriceOutOfBounds.into()
+        );
+    }
+
+    #[test]
+    fn validate_resale_price_uses_original_price_not_current_price() {
+        let mut ticket_type = ticket_type(10_000, 0);
+        ticket_type.current_price = 5_000_000_000;
+        assert!(ticket_type.validate_resale_price(1_000_000_000, 1_000_000_000).is_ok());
+        assert!(ticket_type.validate_resale_price(1_000_000_000, 1_000_000_001).is_err());
+    }
+
+    #[test]
+    fn validate_resale_price_does_not_overflow_on_large_prices() {
+        let ticket_type = ticket_type(u16::MAX, 0);
+        assert!(ticket_type.validate_resale_price(u64::MAX, u64::MAX).is_ok());
+    }
+}
@@ -10,7 +10,18 @@ pub struct PlatformAccount {
     pub platform_fee_bps: u16,
     /// 平台收入账户
     pub fee_recipient: Pubkey,
-    /// 平台是否暂停
+    /// 平台是否暂停（熔断开关）
+    ///
+    /// 暂停期间所有改变状态的指令都会以 PlatformPaused 失败，以下白名单指令除外：
+    /// - setup_platform、add_supported_token、remove_supported_token：平台管理员处理事故及恢复运行
+    /// - withdraw_proceeds：主办方提取已有收益
+    /// - redeem_ticket：活动现场入场核销
+    /// - cancel_ticket_listing：卖家取回托管中的门票
+    /// - close_expired_listing：过期挂单的门票退回卖家
+    /// - withdraw_offer：买家取回托管中的出价金额
+    /// - get_seat_status_batch：只读查询
+    /// - release_expired_holds：释放座位锁定，避免座位在暂停期间被长期占用
+    /// - sync_seat_account：按位图修复座位详细记录，不改变座位状态
     pub is_paused: bool,
     /// 最低票价（防止恶意低价）
     pub min_ticket_price: u64,
@@ -33,6 +44,13 @@ pub struct PlatformAccount {
     pub _reserved: [u8; 63], // 减少1字节给bump
 }
 
+impl PlatformAccount {
+    /// 检查代币是否在平台支持的支付代币列表中
+    pub fn is_supported_token(&self, mint: &Pubkey) -> bool {
+        self.supported_tokens.contains(mint)
+    }
+}
+
 
 
 /// 主办方收益账户
@@ -57,8 +75,16 @@ pub struct OrganizerEarnings {
     pub withdrawal_count: u32,
     /// PDA bump值（用于性能优化）
     pub bump: u8,
+    /// 收款钱包（为空时提取到主办方钱包）
+    ///
+    /// 占用预留空间，升级前创建的账户可直接读取（为空），设置收款钱包前需先调用 migrate_account_layout 扩容
+    pub payout_wallet: Option<Pubkey>,
+    /// 结算币种（None 为 SOL，否则为SPL代币mint，由第一个票种确定）
+    ///
+    /// 占用预留空间，升级前创建的账户可直接读取（为空，即SOL结算），改用SPL代币前需先调用 migrate_account_layout 扩容
+    pub payment_mint: Option<Pubkey>,
     /// 预留空间
-    pub _reserved: [u8; 31], // 减少1字节给bump
+    pub _reserved: [u8; 29], // 减少1字节给bump，1字节给空的payout_wallet，1字节给空的payment_mint
 }
 
  
\ No newline at end of file
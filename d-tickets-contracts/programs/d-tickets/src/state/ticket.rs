@@ -37,8 +37,18 @@ pub struct TicketAccount {
     pub last_transfer_at: Option<i64>,
     /// PDA bump值（用于性能优化）
     pub bump: u8,
+    /// 座位所在的座位状态映射账户（对号入座门票）
+    ///
+    /// 座位字段为空时共占2字节，已从预留空间中扣除，升级前创建的门票账户无需扩容即可读取
+    pub seat_status_map: Option<Pubkey>,
+    /// 座位在座位状态映射中的索引（对号入座门票）
+    pub seat_index: Option<u32>,
+    /// 购票时实际存入活动托管账户的金额（票价扣除平台手续费），退款以此为上限
+    ///
+    /// 占用预留空间，升级前创建的门票读取为0
+    pub organizer_amount: u64,
     /// 预留空间
-    pub _reserved: [u8; 31], // 减少1字节给bump
+    pub _reserved: [u8; 21], // 减少1字节给bump，2字节给空的座位字段，8字节给organizer_amount
 }
 
 
@@ -101,6 +111,35 @@ pub enum TransferType {
     Refund,
 }
 
+/// 钱包购票记录（每个票种每个钱包一个，用于限购）
+#[account]
+#[derive(InitSpace)]
+pub struct WalletPurchaseRecord {
+    /// 购买者钱包
+    pub buyer: Pubkey,
+    /// 票种账户
+    pub ticket_type: Pubkey,
+    /// 累计购买数量（退票不回退，防止反复退票绕过限购）
+    pub purchased_count: u32,
+    /// PDA bump值（用于性能优化）
+    pub bump: u8,
+}
+
+impl WalletPurchaseRecord {
+    /// 记录一次购买，超出票种的每钱包限购数量时报错
+    pub fn record_purchase(&mut self, quantity: u32, max_per_wallet: u32) -> Result<()> {
+        let purchased_count = self.purchased_count
+            .checked_add(quantity)
+            .ok_or(crate::error::TicketError::Overflow)?;
+        require!(
+            max_per_wallet == 0 || purchased_count <= max_per_wallet,
+            crate::error::TicketError::ExceedsPurchaseLimit
+        );
+        self.purchased_count = purchased_count;
+        Ok(())
+    }
+}
+
 /// 市场挂单状态枚举
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
 pub enum ListingStatus {
@@ -140,8 +179,96 @@ pub struct MarketplaceListingAccount {
     pub platform_fee_bps: u16,
     /// PDA bump值（用于性能优化）
     pub bump: u8,
+    /// 挂单到期时间（不晚于活动开始时间）
+    pub expires_at: i64,
     /// 预留空间
-    pub _reserved: [u8; 31], // 减少1字节给bump
+    pub _reserved: [u8; 23], // 减少1字节给bump，8字节给expires_at
+}
+
+impl MarketplaceListingAccount {
+    /// 挂单是否已过期（升级前创建的挂单 expires_at 为0，视为永不过期）
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        self.expires_at != 0 && current_time >= self.expires_at
+    }
+}
+
+/// 票务市场求购出价（买家将出价金额托管在出价PDA中）
+#[account]
+#[derive(InitSpace)]
+pub struct MarketplaceOfferAccount {
+    /// 出价买家
+    pub buyer: Pubkey,
+    /// 所属活动
+    pub event: Pubkey,
+    /// 所属票种
+    pub ticket_type: Pubkey,
+    /// 指定的门票mint（None 表示该票种下任意门票）
+    pub ticket_mint: Option<Pubkey>,
+    /// 出价编号（同一买家对同一票种可有多个出价）
+    pub offer_id: u64,
+    /// 出价金额（已托管）
+    pub price: u64,
+    /// 结算代币（None 为 SOL，否则为SPL代币mint）
+    pub payment_mint: Option<Pubkey>,
+    /// 出价时间
+    pub created_at: i64,
+    /// 出价到期时间（不晚于活动开始时间）
+    pub expires_at: i64,
+    /// PDA bump值
+    pub bump: u8,
+}
+
+impl MarketplaceOfferAccount {
+    /// 出价是否已过期
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time >= self.expires_at
+    }
 }
 
- 
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use crate::error::TicketError;
+    use crate::test_fixtures::{marketplace_listing, purchase_record};
+
+    #[test]
+    fn record_purchase_accumulates_up_to_the_cap() {
+        let mut record = purchase_record(0);
+        record.record_purchase(2, 4).unwrap();
+        record.record_purchase(2, 4).unwrap();
+        assert_eq!(record.purchased_count, 4);
+    }
+
+    #[test]
+    fn record_purchase_rejects_exceeding_the_cap_without_counting() {
+        let mut record = purchase_record(3);
+        assert_eq!(
+            record.record_purchase(2, 4).unwrap_err(),
+            TicketError::ExceedsPurchaseLimit.into()
+        );
+        assert_eq!(record.purchased_count, 3);
+    }
+
+    #[test]
+    fn record_purchase_without_cap_only_checks_overflow() {
+        let mut record = purchase_record(u32::MAX - 1);
+        record.record_purchase(1, 0).unwrap();
+        assert_eq!(record.purchased_count, u32::MAX);
+        assert_eq!(
+            record.record_purchase(1, 0).unwrap_err(),
+            TicketError::Overflow.into()
+        );
+    }
+
+    #[test]
+    fn listing_expires_at_its_deadline() {
+        let listing = marketplace_listing(1_000);
+        assert!(!listing.is_expired(999));
+        assert!(listing.is_expired(1_000));
+    }
+
+    #[test]
+    fn legacy_listing_without_deadline_never_expires() {
+        let listing = marketplace_listing(0);
+        assert!(!listing.is_expired(i64::MAX));
+    }
+}
@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 use crate::error::TicketError;
+use crate::constants::{
+    BASIS_POINTS_DIVISOR, MAX_SEATS_PER_AREA, MAX_SEAT_PRICE_MULTIPLIER_BPS, MAX_SEAT_PRICE_TIERS,
+    MAX_TICKET_PRICE, MIN_TICKET_PRICE, SEATS_PER_BITMAP_PAGE,
+};
 
 /// 场馆账户状态 - 使用 InitSpace 自动计算空间
 #[account]
@@ -113,7 +117,22 @@ pub enum SeatStatus {
     Unavailable,
 }
 
+impl SeatStatus {
+    /// 座位是否处于已售出状态（已售出或已核销）
+    pub fn is_sold(&self) -> bool {
+        matches!(self, SeatStatus::Sold | SeatStatus::Redeemed)
+    }
+
+    /// 座位是否可以出售（可用或已退票）
+    pub fn is_purchasable(&self) -> bool {
+        matches!(self, SeatStatus::Available | SeatStatus::Refunded)
+    }
+}
+
 /// 单个座位信息（用于链上状态追踪）- 使用 InitSpace
+///
+/// 关联到座位状态映射后作为位图中对应座位的详细记录，座位状态以位图为准，
+/// 由 batch_update_seat_status 同步更新或通过 sync_seat_account 校验修复
 #[account]
 #[derive(InitSpace)]
 pub struct SeatAccount {
@@ -147,6 +166,24 @@ pub struct SeatAccount {
     pub updated_at: i64,
     /// PDA bump值
     pub bump: u8,
+    /// 关联的座位状态映射（关联后座位状态以位图为准）
+    ///
+    /// 以下字段追加在原有字段之后，升级前创建的座位账户需先调用 migrate_account_layout 扩容
+    pub seat_status_map: Option<Pubkey>,
+    /// 在座位状态映射中的座位索引
+    pub seat_index: Option<u32>,
+}
+
+impl SeatAccount {
+    /// 按位图中的座位状态同步详细记录，返回记录是否与位图不一致
+    pub fn sync_status(&mut self, status: SeatStatus, current_time: i64) -> bool {
+        if self.seat_status == status {
+            return false;
+        }
+        self.seat_status = status;
+        self.updated_at = current_time;
+        true
+    }
 }
 
 /// 批量座位数据结构
@@ -166,6 +203,38 @@ pub struct SeatData {
     pub seat_number_in_row: String,
 }
 
+/// 座位排布局（一排中连续的座位索引）
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
+pub struct SeatRow {
+    /// 本排第一个座位的索引
+    pub start_index: u32,
+    /// 本排座位数量
+    pub seat_count: u32,
+    /// 排号
+    #[max_len(10)]
+    pub row_label: String,
+}
+
+/// 座位价格档位的定价方式
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub enum SeatTierPrice {
+    /// 按票种当前价格的倍数定价（基点，10000为原价）
+    Multiplier { bps: u32 },
+    /// 固定价格（不随票种动态定价变化）
+    Fixed { price: u64 },
+}
+
+/// 座位价格档位（一段连续的座位索引）
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
+pub struct SeatPriceTier {
+    /// 档位第一个座位的索引
+    pub start_index: u32,
+    /// 档位座位数量
+    pub seat_count: u32,
+    /// 定价方式
+    pub price: SeatTierPrice,
+}
+
 /// 座位状态更新数据结构
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
 pub struct SeatStatusUpdate {
@@ -173,12 +242,25 @@ pub struct SeatStatusUpdate {
     pub seat_index: u32,
     /// 新的座位状态
     pub new_status: SeatStatus,
-    /// 购票者地址（仅当状态为 Sold 时需要）
+    /// 购票者地址（已不再使用，售座请调用 purchase_seat）
     pub buyer: Option<Pubkey>,
-    /// 座位详细信息（用于 NFT 元数据）
+    /// 座位详细信息（已不再使用，售座请调用 purchase_seat）
     pub seat_info: Option<SeatInfo>,
 }
 
+/// 单个座位账户状态更新数据结构
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SeatAccountUpdate {
+    /// 新的座位状态
+    pub new_status: SeatStatus,
+    /// 关联活动（为空时保持不变）
+    pub event_key: Option<Pubkey>,
+    /// 关联门票NFT（为空时保持不变）
+    pub ticket_nft: Option<Pubkey>,
+    /// 关联票种ID（为空时保持不变）
+    pub ticket_type_id: Option<u8>,
+}
+
 /// 座位详细信息结构体（用于 NFT 元数据）
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
 pub struct SeatInfo {
@@ -197,7 +279,10 @@ pub struct SeatInfo {
 }
 
 /// 座位状态管理账户 - 使用位图高效存储
-#[account]
+///
+/// 位图全部存放在 SeatStatusPage 分页账户中。当前布局使用新的discriminator
+/// （sha256("account:SeatStatusMapV2") 的前8字节），旧版内联位图账户需先通过 migrate_seat_status_map 迁移
+#[account(discriminator = [95, 0, 142, 228, 212, 41, 39, 168])]
 #[derive(InitSpace)]
 pub struct SeatStatusMap {
     /// 所属活动
@@ -210,92 +295,576 @@ pub struct SeatStatusMap {
     /// 座位索引映射IPFS哈希
     #[max_len(100)]
     pub seat_index_map_hash: String,
-    /// 总座位数量
+    /// 总座位数量（含分页账户中的座位）
     pub total_seats: u32,
-    /// 已售座位数量
+    /// 已售座位数量（已售出及已核销）
     pub sold_seats: u32,
-    /// 座位状态位图（每座位2位：00=可用，01=已售，10=锁定，11=不可用）
-    /// 最多支持16000个座位（4000字节 * 8位 / 2位 = 16000座位）
-    #[max_len(4000)]
-    pub seat_status_bitmap: Vec<u8>,
     /// 创建时间
     pub created_at: i64,
     /// 更新时间
     pub updated_at: i64,
     /// PDA bump
     pub bump: u8,
+    /// 是否已登记链上座位布局（登记后售座时由布局推导排号和座位号）
+    pub has_seat_layout: bool,
+    /// 是否已设置座位价格档位（设置后售座时按档位计价）
+    pub has_price_tiers: bool,
 }
 
 impl SeatStatusMap {
-    /// 初始化座位状态位图
+    /// 旧版（内联每座位2位位图）账户的discriminator，即 sha256("account:SeatStatusMap") 的前8字节
+    pub const LEGACY_DISCRIMINATOR: [u8; 8] = [126, 136, 168, 128, 22, 10, 212, 80];
+
+    /// 初始化座位状态位图（分页账户由 create_seat_status_page 单独初始化）
     pub fn initialize_bitmap(&mut self, total_seats: u32) -> Result<()> {
-        let bytes_needed = ((total_seats + 3) / 4) as usize; // 每4个座位需要1字节
-        require!(bytes_needed <= 4000, TicketError::TooManySeats);
+        require!(total_seats <= MAX_SEATS_PER_AREA, TicketError::TooManySeats);
         
-        self.seat_status_bitmap = vec![0u8; bytes_needed];
         self.total_seats = total_seats;
         Ok(())
     }
+
+    /// 座位所在的分页序号
+    pub fn page_index_of(seat_index: u32) -> u16 {
+        (seat_index / SEATS_PER_BITMAP_PAGE) as u16
+    }
+
+    /// 分页中第一个座位的索引
+    pub fn page_start_index(page_index: u16) -> u32 {
+        page_index as u32 * SEATS_PER_BITMAP_PAGE
+    }
+
+    /// 分页序号是否在座位总数范围内
+    pub fn is_valid_page(&self, page_index: u16) -> bool {
+        Self::page_start_index(page_index) < self.total_seats
+    }
     
-    /// 获取座位状态
-    pub fn get_seat_status(&self, seat_index: u32) -> Result<SeatStatus> {
+    /// 获取座位状态（需传入座位所在的分页账户）
+    pub fn get_seat_status(&self, page: &SeatStatusPage, seat_index: u32) -> Result<SeatStatus> {
         require!(seat_index < self.total_seats, TicketError::InvalidSeatIndex);
-        
-        let byte_index = (seat_index / 4) as usize;
-        let bit_index = (seat_index % 4) * 2;
-        
-        require!(byte_index < self.seat_status_bitmap.len(), TicketError::InvalidSeatIndex);
-        
-        let status_byte = self.seat_status_bitmap[byte_index];
-        let status_bits = (status_byte >> bit_index) & 0x03;
-        
-        match status_bits {
-            0 => Ok(SeatStatus::Available),
-            1 => Ok(SeatStatus::Sold),
-            2 => Ok(SeatStatus::TempLocked),
-            3 => Ok(SeatStatus::Unavailable),
-            _ => Err(TicketError::InvalidSeatStatus.into()),
-        }
+
+        require!(
+            page.page_index == Self::page_index_of(seat_index),
+            TicketError::SeatStatusPageRequired
+        );
+        require!(
+            seat_index - page.start_index < page.seat_count,
+            TicketError::InvalidSeatIndex
+        );
+        read_seat_status(&page.seat_status_bitmap, seat_index - page.start_index)
     }
     
-    /// 更新座位状态
-    pub fn update_seat_status(&mut self, seat_index: u32, new_status: SeatStatus) -> Result<()> {
-        require!(seat_index < self.total_seats, TicketError::InvalidSeatIndex);
-        
-        let old_status = self.get_seat_status(seat_index)?;
-        
-        let byte_index = (seat_index / 4) as usize;
-        let bit_index = (seat_index % 4) * 2;
-        
-        let status_bits = match new_status {
-            SeatStatus::Available => 0,
-            SeatStatus::Sold => 1,
-            SeatStatus::TempLocked => 2,
-            SeatStatus::Unavailable => 3,
-            _ => return Err(TicketError::InvalidSeatStatus.into()),
-        };
-        
-        // 清除原状态位
-        self.seat_status_bitmap[byte_index] &= !(0x03 << bit_index);
-        // 设置新状态位
-        self.seat_status_bitmap[byte_index] |= status_bits << bit_index;
+    /// 更新座位状态（需传入座位所在的分页账户，直接在分页账户数据上修改）
+    pub fn update_seat_status(
+        &mut self,
+        page: &mut SeatStatusPage,
+        seat_index: u32,
+        new_status: SeatStatus,
+    ) -> Result<()> {
+        let old_status = self.get_seat_status(page, seat_index)?;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        write_seat_status(&mut page.seat_status_bitmap, seat_index - page.start_index, &new_status)?;
+        page.updated_at = current_time;
         
-        // 更新计数器
-        if old_status == SeatStatus::Sold && new_status != SeatStatus::Sold {
+        // 更新计数器（已核销的座位仍计为已售）
+        if old_status.is_sold() && !new_status.is_sold() {
             self.sold_seats = self.sold_seats.saturating_sub(1);
-        } else if old_status != SeatStatus::Sold && new_status == SeatStatus::Sold {
+        } else if !old_status.is_sold() && new_status.is_sold() {
             self.sold_seats += 1;
         }
         
-        self.updated_at = Clock::get()?.unix_timestamp;
+        self.updated_at = current_time;
         Ok(())
     }
     
-    /// 批量更新座位状态
-    pub fn batch_update_status(&mut self, updates: Vec<SeatStatusUpdate>) -> Result<()> {
+    /// 计算座位售价：已设置价格档位时按档位计价，否则为票种当前价格
+    pub fn seat_price(
+        &self,
+        price_tiers: Option<&SeatPriceTierAccount>,
+        base_price: u64,
+        seat_index: u32,
+    ) -> Result<u64> {
+        match price_tiers {
+            Some(price_tiers) => price_tiers.price_for(seat_index, base_price),
+            None => {
+                require!(!self.has_price_tiers, TicketError::SeatPriceTiersRequired);
+                Ok(base_price)
+            }
+        }
+    }
+
+    /// 批量更新同一分页中的座位状态
+    pub fn batch_update_status(&mut self, page: &mut SeatStatusPage, updates: Vec<SeatStatusUpdate>) -> Result<()> {
         for update in updates {
-            self.update_seat_status(update.seat_index, update.new_status)?;
+            self.update_seat_status(page, update.seat_index, update.new_status)?;
+        }
+        Ok(())
+    }
+}
+
+/// 座位状态分页账户（每页16000个座位，第N页覆盖座位索引 N*16000 起）
+///
+/// 使用 zero-copy 布局，通过 AccountLoader 直接在账户数据上读写位图，
+/// 批量更新时不需要反序列化和重新序列化整个位图
+#[account(zero_copy)]
+pub struct SeatStatusPage {
+    /// 所属座位状态映射
+    pub seat_status_map: Pubkey,
+    /// 创建时间
+    pub created_at: i64,
+    /// 更新时间
+    pub updated_at: i64,
+    /// 本页第一个座位的索引
+    pub start_index: u32,
+    /// 本页座位数量
+    pub seat_count: u32,
+    /// 分页序号（从0开始）
+    pub page_index: u16,
+    /// PDA bump
+    pub bump: u8,
+    /// 对齐填充
+    pub _padding: [u8; 5],
+    /// 座位状态位图（编码与 SeatStatusMap 相同）
+    pub seat_status_bitmap: [u8; 8000],
+}
+
+impl SeatStatusPage {
+    /// 账户空间（不含discriminator）
+    pub const SPACE: usize = std::mem::size_of::<SeatStatusPage>();
+}
+
+/// 将旧版内联位图（每座位2位）转换写入分页位图（每座位4位），旧版状态值在新编码中保持不变
+///
+/// 旧版的临时锁定没有对应的选座锁定账户、无法通过 release_expired_holds 释放，迁移时恢复为可用
+pub fn migrate_bitmap(legacy_bitmap: &[u8], total_seats: u32, page_bitmap: &mut [u8]) -> Result<()> {
+    require!(total_seats <= SEATS_PER_BITMAP_PAGE, TicketError::TooManySeats);
+    require!(
+        legacy_bitmap.len() >= total_seats.div_ceil(4) as usize,
+        TicketError::InvalidSeatIndex
+    );
+
+    for seat_index in 0..total_seats {
+        let legacy_byte = legacy_bitmap[(seat_index / 4) as usize];
+        let status_bits = match (legacy_byte >> ((seat_index % 4) * 2)) & 0x03 {
+            2 => 0,
+            bits => bits,
+        };
+        let byte_index = (seat_index / 2) as usize;
+        require!(byte_index < page_bitmap.len(), TicketError::InvalidSeatIndex);
+        let bit_index = (seat_index % 2) * 4;
+        page_bitmap[byte_index] &= !(0x0F << bit_index);
+        page_bitmap[byte_index] |= status_bits << bit_index;
+    }
+    Ok(())
+}
+
+/// 读取位图中的座位状态（每座位4位）
+fn read_seat_status(bitmap: &[u8], local_index: u32) -> Result<SeatStatus> {
+    let byte_index = (local_index / 2) as usize;
+    let bit_index = (local_index % 2) * 4;
+
+    require!(byte_index < bitmap.len(), TicketError::InvalidSeatIndex);
+
+    match (bitmap[byte_index] >> bit_index) & 0x0F {
+        0 => Ok(SeatStatus::Available),
+        1 => Ok(SeatStatus::Sold),
+        2 => Ok(SeatStatus::TempLocked),
+        3 => Ok(SeatStatus::Unavailable),
+        4 => Ok(SeatStatus::Refunded),
+        5 => Ok(SeatStatus::Redeemed),
+        _ => Err(TicketError::InvalidSeatStatus.into()),
+    }
+}
+
+/// 写入位图中的座位状态（每座位4位）
+fn write_seat_status(bitmap: &mut [u8], local_index: u32, status: &SeatStatus) -> Result<()> {
+    let byte_index = (local_index / 2) as usize;
+    let bit_index = (local_index % 2) * 4;
+
+    require!(byte_index < bitmap.len(), TicketError::InvalidSeatIndex);
+
+    let status_bits = match status {
+        SeatStatus::Available => 0,
+        SeatStatus::Sold => 1,
+        SeatStatus::TempLocked => 2,
+        SeatStatus::Unavailable => 3,
+        SeatStatus::Refunded => 4,
+        SeatStatus::Redeemed => 5,
+    };
+
+    // 清除原状态位
+    bitmap[byte_index] &= !(0x0F << bit_index);
+    // 设置新状态位
+    bitmap[byte_index] |= status_bits << bit_index;
+    Ok(())
+}
+
+/// 座位布局账户（每个座位状态映射一个，记录座位索引到排号和座位号的对应关系）
+#[account]
+#[derive(InitSpace)]
+pub struct SeatLayoutAccount {
+    /// 所属座位状态映射
+    pub seat_status_map: Pubkey,
+    /// 各排布局（按起始索引升序排列，互不重叠）
+    #[max_len(200)]
+    pub rows: Vec<SeatRow>,
+    /// 创建时间
+    pub created_at: i64,
+    /// 更新时间
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SeatLayoutAccount {
+    /// 验证各排布局：座位数不为0、排号长度合法、按起始索引升序且不超出座位总数
+    pub fn validate_rows(rows: &[SeatRow], total_seats: u32) -> Result<()> {
+        let mut next_free_index = 0u32;
+        for row in rows {
+            require!(row.seat_count > 0, TicketError::InvalidSeatLayout);
+            require!(
+                !row.row_label.is_empty() && row.row_label.len() <= 10,
+                TicketError::RowNumberTooLong
+            );
+            require!(row.start_index >= next_free_index, TicketError::InvalidSeatLayout);
+            next_free_index = row.start_index
+                .checked_add(row.seat_count)
+                .ok_or(TicketError::Overflow)?;
+            require!(next_free_index <= total_seats, TicketError::InvalidSeatLayout);
+        }
+        Ok(())
+    }
+
+    /// 由座位索引推导排号和排内座位号（座位号从1开始）
+    pub fn seat_label(&self, seat_index: u32) -> Result<(String, String)> {
+        let position = self.rows.partition_point(|row| row.start_index <= seat_index);
+        let row = position
+            .checked_sub(1)
+            .map(|position| &self.rows[position])
+            .filter(|row| seat_index - row.start_index < row.seat_count)
+            .ok_or(TicketError::SeatNotFound)?;
+        Ok((row.row_label.clone(), (seat_index - row.start_index + 1).to_string()))
+    }
+}
+
+/// 座位价格档位账户（每个座位状态映射一个，未覆盖的座位按票种当前价格出售）
+#[account]
+#[derive(InitSpace)]
+pub struct SeatPriceTierAccount {
+    /// 所属座位状态映射
+    pub seat_status_map: Pubkey,
+    /// 价格档位（按起始索引升序排列，互不重叠）
+    #[max_len(20)]
+    pub tiers: Vec<SeatPriceTier>,
+    /// 创建时间
+    pub created_at: i64,
+    /// 更新时间
+    pub updated_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SeatPriceTierAccount {
+    /// 验证价格档位：座位数不为0、按起始索引升序且不超出座位总数、价格在允许范围内
+    pub fn validate_tiers(tiers: &[SeatPriceTier], total_seats: u32) -> Result<()> {
+        require!(tiers.len() <= MAX_SEAT_PRICE_TIERS, TicketError::InvalidSeatPriceTier);
+        let mut next_free_index = 0u32;
+        for tier in tiers {
+            require!(tier.seat_count > 0, TicketError::InvalidSeatPriceTier);
+            require!(tier.start_index >= next_free_index, TicketError::InvalidSeatPriceTier);
+            next_free_index = tier.start_index
+                .checked_add(tier.seat_count)
+                .ok_or(TicketError::Overflow)?;
+            require!(next_free_index <= total_seats, TicketError::InvalidSeatPriceTier);
+
+            match tier.price {
+                SeatTierPrice::Multiplier { bps } => require!(
+                    bps > 0 && bps <= MAX_SEAT_PRICE_MULTIPLIER_BPS,
+                    TicketError::InvalidSeatPriceTier
+                ),
+                SeatTierPrice::Fixed { price } => require!(
+                    (MIN_TICKET_PRICE..=MAX_TICKET_PRICE).contains(&price),
+                    TicketError::InvalidPrice
+                ),
+            }
         }
         Ok(())
     }
-} 
\ No newline at end of file
+
+    /// 计算座位售价（未被任何档位覆盖的座位按票种当前价格）
+    pub fn price_for(&self, seat_index: u32, base_price: u64) -> Result<u64> {
+        let position = self.tiers.partition_point(|tier| tier.start_index <= seat_index);
+        let tier = position
+            .checked_sub(1)
+            .map(|position| &self.tiers[position])
+            .filter(|tier| seat_index - tier.start_index < tier.seat_count);
+
+        match tier.map(|tier| &tier.price) {
+            Some(SeatTierPrice::Multiplier { bps }) => {
+                let price = (base_price as u128)
+                    .checked_mul(*bps as u128)
+                    .ok_or(TicketError::Overflow)?
+                    / BASIS_POINTS_DIVISOR as u128;
+                u64::try_from(price).map_err(|_| TicketError::Overflow.into())
+            }
+            Some(SeatTierPrice::Fixed { price }) => Ok(*price),
+            None => Ok(base_price),
+        }
+    }
+}
+
+/// 选座锁定账户（购票结算期间临时锁定座位）
+#[account]
+#[derive(InitSpace)]
+pub struct SeatHold {
+    /// 锁定座位的买家
+    pub buyer: Pubkey,
+    /// 所属座位状态映射
+    pub seat_status_map: Pubkey,
+    /// 被锁定的座位索引（购买后移除）
+    #[max_len(10)]
+    pub seat_indices: Vec<u32>,
+    /// 各座位锁定前的状态（与 seat_indices 一一对应，释放时恢复）
+    #[max_len(10)]
+    pub prior_statuses: Vec<SeatStatus>,
+    /// 锁定到期时间
+    pub expires_at: i64,
+    /// 创建时间
+    pub created_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SeatHold {
+    /// 锁定是否已过期
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time >= self.expires_at
+    }
+
+    /// 从锁定中取出座位（购买时消耗锁定）
+    pub fn take_seat(&mut self, seat_index: u32) -> Result<()> {
+        let position = self.seat_indices
+            .iter()
+            .position(|index| *index == seat_index)
+            .ok_or(TicketError::SeatNotHeld)?;
+        self.seat_indices.swap_remove(position);
+        self.prior_statuses.swap_remove(position);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{
+        price_tier, price_tier_account, seat_hold, seat_layout, seat_row, seat_status_map, seat_status_page,
+    };
+
+    #[test]
+    fn take_seat_consumes_held_seats_once() {
+        let mut hold = seat_hold(vec![3, 7, 11], 100);
+        hold.prior_statuses = vec![SeatStatus::Available, SeatStatus::Available, SeatStatus::Refunded];
+
+        hold.take_seat(7).unwrap();
+        assert_eq!(hold.seat_indices, vec![3, 11]);
+        assert_eq!(hold.prior_statuses, vec![SeatStatus::Available, SeatStatus::Refunded]);
+        assert_eq!(hold.take_seat(7).unwrap_err(), TicketError::SeatNotHeld.into());
+        assert_eq!(hold.take_seat(4).unwrap_err(), TicketError::SeatNotHeld.into());
+
+        hold.take_seat(3).unwrap();
+        hold.take_seat(11).unwrap();
+        assert!(hold.seat_indices.is_empty());
+        assert!(!hold.is_expired(99));
+        assert!(hold.is_expired(100));
+    }
+
+    #[test]
+    fn get_seat_status_reads_four_bit_statuses_from_the_page() {
+        // 每座位4位：座位0已售、1锁定、2已退票、3已核销、4可用
+        let map = seat_status_map(5);
+        let mut page = seat_status_page(0, 5);
+        page.seat_status_bitmap[..3].copy_from_slice(&[0x21, 0x54, 0x00]);
+
+        assert_eq!(map.get_seat_status(&page, 0).unwrap(), SeatStatus::Sold);
+        assert_eq!(map.get_seat_status(&page, 1).unwrap(), SeatStatus::TempLocked);
+        assert_eq!(map.get_seat_status(&page, 2).unwrap(), SeatStatus::Refunded);
+        assert_eq!(map.get_seat_status(&page, 3).unwrap(), SeatStatus::Redeemed);
+        assert_eq!(map.get_seat_status(&page, 4).unwrap(), SeatStatus::Available);
+        assert_eq!(map.get_seat_status(&page, 5).unwrap_err(), TicketError::InvalidSeatIndex.into());
+    }
+
+    #[test]
+    fn get_seat_status_requires_the_matching_page() {
+        let map = seat_status_map(SEATS_PER_BITMAP_PAGE + 10);
+        let first_page = seat_status_page(0, SEATS_PER_BITMAP_PAGE);
+        let second_page = seat_status_page(1, 10);
+
+        assert_eq!(
+            map.get_seat_status(&first_page, SEATS_PER_BITMAP_PAGE).unwrap_err(),
+            TicketError::SeatStatusPageRequired.into()
+        );
+        assert_eq!(
+            map.get_seat_status(&second_page, SEATS_PER_BITMAP_PAGE + 9).unwrap(),
+            SeatStatus::Available
+        );
+    }
+
+    #[test]
+    fn migrate_bitmap_preserves_legacy_statuses_and_releases_locks() {
+        // 旧版编码（每座位2位）：座位0可用、1已售、2锁定、3不可用；座位4已售
+        let legacy_bitmap = [0b11_10_01_00, 0b00_00_00_01];
+        let mut page_bitmap = [0xFFu8; 4];
+
+        migrate_bitmap(&legacy_bitmap, 5, &mut page_bitmap).unwrap();
+        assert_eq!(page_bitmap, [0x10, 0x30, 0xF1, 0xFF]);
+        assert_eq!(read_seat_status(&page_bitmap, 0).unwrap(), SeatStatus::Available);
+        assert_eq!(read_seat_status(&page_bitmap, 1).unwrap(), SeatStatus::Sold);
+        assert_eq!(read_seat_status(&page_bitmap, 2).unwrap(), SeatStatus::Available);
+        assert_eq!(read_seat_status(&page_bitmap, 3).unwrap(), SeatStatus::Unavailable);
+        assert_eq!(read_seat_status(&page_bitmap, 4).unwrap(), SeatStatus::Sold);
+    }
+
+    #[test]
+    fn migrate_bitmap_rejects_short_buffers() {
+        let mut page_bitmap = [0u8; 4];
+        assert_eq!(
+            migrate_bitmap(&[0u8; 1], 5, &mut page_bitmap).unwrap_err(),
+            TicketError::InvalidSeatIndex.into()
+        );
+        assert_eq!(
+            migrate_bitmap(&[0u8; 3], 9, &mut page_bitmap).unwrap_err(),
+            TicketError::InvalidSeatIndex.into()
+        );
+        assert_eq!(
+            migrate_bitmap(&[], SEATS_PER_BITMAP_PAGE + 1, &mut page_bitmap).unwrap_err(),
+            TicketError::TooManySeats.into()
+        );
+    }
+
+    #[test]
+    fn write_seat_status_round_trips_without_touching_neighbours() {
+        let statuses = [
+            SeatStatus::Available,
+            SeatStatus::Sold,
+            SeatStatus::TempLocked,
+            SeatStatus::Unavailable,
+            SeatStatus::Refunded,
+            SeatStatus::Redeemed,
+        ];
+        let mut bitmap = [0u8; 3];
+
+        for (index, status) in statuses.iter().enumerate() {
+            write_seat_status(&mut bitmap, index as u32, status).unwrap();
+        }
+        for (index, status) in statuses.iter().enumerate() {
+            assert_eq!(&read_seat_status(&bitmap, index as u32).unwrap(), status);
+        }
+
+        write_seat_status(&mut bitmap, 4, &SeatStatus::Available).unwrap();
+        assert_eq!(read_seat_status(&bitmap, 4).unwrap(), SeatStatus::Available);
+        assert_eq!(read_seat_status(&bitmap, 5).unwrap(), SeatStatus::Redeemed);
+    }
+
+    #[test]
+    fn read_seat_status_rejects_out_of_range_and_unknown_values() {
+        let mut bitmap = [0x06u8];
+        assert_eq!(
+            read_seat_status(&bitmap, 0).unwrap_err(),
+            TicketError::InvalidSeatStatus.into()
+        );
+        assert_eq!(
+            read_seat_status(&bitmap, 2).unwrap_err(),
+            TicketError::InvalidSeatIndex.into()
+        );
+        assert_eq!(
+            write_seat_status(&mut bitmap, 2, &SeatStatus::Sold).unwrap_err(),
+            TicketError::InvalidSeatIndex.into()
+        );
+    }
+
+    #[test]
+    fn validate_rows_accepts_ordered_rows_with_gaps() {
+        let rows = [seat_row(0, 10, "A"), seat_row(12, 8, "B")];
+        assert!(SeatLayoutAccount::validate_rows(&rows, 20).is_ok());
+        assert!(SeatLayoutAccount::validate_rows(&[], 0).is_ok());
+    }
+
+    #[test]
+    fn validate_rows_rejects_invalid_rows() {
+        let cases = [
+            (vec![seat_row(0, 0, "A")], TicketError::InvalidSeatLayout),
+            (vec![seat_row(0, 10, "A"), seat_row(9, 5, "B")], TicketError::InvalidSeatLayout),
+            (vec![seat_row(0, 21, "A")], TicketError::InvalidSeatLayout),
+            (vec![seat_row(0, 10, "")], TicketError::RowNumberTooLong),
+            (vec![seat_row(0, 10, "ABCDEFGHIJK")], TicketError::RowNumberTooLong),
+            (vec![seat_row(u32::MAX, 1, "A")], TicketError::Overflow),
+        ];
+        for (rows, error) in cases {
+            assert_eq!(SeatLayoutAccount::validate_rows(&rows, 20).unwrap_err(), error.into());
+        }
+    }
+
+    #[test]
+    fn seat_label_derives_row_and_seat_number() {
+        let layout = seat_layout(vec![seat_row(0, 10, "A"), seat_row(12, 8, "B")]);
+
+        assert_eq!(layout.seat_label(0).unwrap(), ("A".to_string(), "1".to_string()));
+        assert_eq!(layout.seat_label(9).unwrap(), ("A".to_string(), "10".to_string()));
+        assert_eq!(layout.seat_label(12).unwrap(), ("B".to_string(), "1".to_string()));
+        assert_eq!(layout.seat_label(19).unwrap(), ("B".to_string(), "8".to_string()));
+        for seat_index in [10, 11, 20] {
+            assert_eq!(layout.seat_label(seat_index).unwrap_err(), TicketError::SeatNotFound.into());
+        }
+    }
+
+    #[test]
+    fn validate_tiers_accepts_ordered_tiers_within_limits() {
+        let tiers = [
+            price_tier(0, 10, SeatTierPrice::Multiplier { bps: MAX_SEAT_PRICE_MULTIPLIER_BPS }),
+            price_tier(10, 10, SeatTierPrice::Fixed { price: MIN_TICKET_PRICE }),
+        ];
+        assert!(SeatPriceTierAccount::validate_tiers(&tiers, 20).is_ok());
+    }
+
+    #[test]
+    fn validate_tiers_rejects_invalid_tiers() {
+        let multiplier = SeatTierPrice::Multiplier { bps: 10_000 };
+        let cases = [
+            (vec![price_tier(0, 0, multiplier.clone())], TicketError::InvalidSeatPriceTier),
+            (
+                vec![price_tier(0, 10, multiplier.clone()), price_tier(5, 5, multiplier.clone())],
+                TicketError::InvalidSeatPriceTier,
+            ),
+            (vec![price_tier(0, 21, multiplier.clone())], TicketError::InvalidSeatPriceTier),
+            (vec![price_tier(0, 1, SeatTierPrice::Multiplier { bps: 0 })], TicketError::InvalidSeatPriceTier),
+            (
+                vec![price_tier(0, 1, SeatTierPrice::Multiplier { bps: MAX_SEAT_PRICE_MULTIPLIER_BPS + 1 })],
+                TicketError::InvalidSeatPriceTier,
+            ),
+            (vec![price_tier(0, 1, SeatTierPrice::Fixed { price: MIN_TICKET_PRICE - 1 })], TicketError::InvalidPrice),
+            (vec![price_tier(0, 1, SeatTierPrice::Fixed { price: MAX_TICKET_PRICE + 1 })], TicketError::InvalidPrice),
+            (vec![price_tier(0, 1, multiplier.clone()); MAX_SEAT_PRICE_TIERS + 1], TicketError::InvalidSeatPriceTier),
+        ];
+        for (tiers, error) in cases {
+            assert_eq!(SeatPriceTierAccount::validate_tiers(&tiers, 20).unwrap_err(), error.into());
+        }
+    }
+
+    #[test]
+    fn price_for_applies_matching_tier_or_base_price() {
+        let price_tiers = price_tier_account(vec![
+            price_tier(0, 10, SeatTierPrice::Multiplier { bps: 15_000 }),
+            price_tier(20, 5, SeatTierPrice::Fixed { price: 3_000_000 }),
+        ]);
+
+        assert_eq!(price_tiers.price_for(0, 2_000_000).unwrap(), 3_000_000);
+        assert_eq!(price_tiers.price_for(9, 2_000_000).unwrap(), 3_000_000);
+        assert_eq!(price_tiers.price_for(10, 2_000_000).unwrap(), 2_000_000);
+        assert_eq!(price_tiers.price_for(24, 2_000_000).unwrap(), 3_000_000);
+        assert_eq!(price_tiers.price_for(25, 2_000_000).unwrap(), 2_000_000);
+        assert_eq!(
+            price_tiers.price_for(0, u64::MAX).unwrap_err(),
+            TicketError::Overflow.into()
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! 单元测试共用的账户构造函数
+
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// 钱包购票记录（已购买 purchased_count 张）
+pub fn purchase_record(purchased_count: u32) -> WalletPurchaseRecord {
+    WalletPurchaseRecord {
+        buyer: Pubkey::default(),
+        ticket_type: Pubkey::default(),
+        purchased_count,
+        bump: 255,
+    }
+}
+
+/// 活跃的市场挂单（expires_at 到期）
+pub fn marketplace_listing(expires_at: i64) -> MarketplaceListingAccount {
+    MarketplaceListingAccount {
+        ticket_mint: Pubkey::default(),
+        seller: Pubkey::default(),
+        price: 0,
+        listed_at: 0,
+        status: ListingStatus::Active,
+        buyer: None,
+        sold_at: None,
+        sold_price: None,
+        royalty_bps: 0,
+        platform_fee_bps: 0,
+        bump: 255,
+        expires_at,
+        _reserved: [0; 23],
+    }
+}
+
+/// 选座锁定（锁定 seat_indices，expires_at 到期）
+pub fn seat_hold(seat_indices: Vec<u32>, expires_at: i64) -> SeatHold {
+    SeatHold {
+        buyer: Pubkey::default(),
+        seat_status_map: Pubkey::default(),
+        prior_statuses: vec![SeatStatus::Available; seat_indices.len()],
+        seat_indices,
+        expires_at,
+        created_at: 0,
+        bump: 255,
+    }
+}
+
+/// 座位状态映射（共 total_seats 个座位）
+pub fn seat_status_map(total_seats: u32) -> SeatStatusMap {
+    SeatStatusMap {
+        event: Pubkey::default(),
+        ticket_type: Pubkey::default(),
+        seat_layout_hash: String::new(),
+        seat_index_map_hash: String::new(),
+        total_seats,
+        sold_seats: 0,
+        created_at: 0,
+        updated_at: 0,
+        bump: 255,
+        has_seat_layout: false,
+        has_price_tiers: false,
+    }
+}
+
+/// 座位状态分页（第 page_index 页，位图全部为可用）
+pub fn seat_status_page(page_index: u16, seat_count: u32) -> SeatStatusPage {
+    SeatStatusPage {
+        seat_status_map: Pubkey::default(),
+        created_at: 0,
+        updated_at: 0,
+        start_index: SeatStatusMap::page_start_index(page_index),
+        seat_count,
+        page_index,
+        bump: 255,
+        _padding: [0; 5],
+        seat_status_bitmap: [0; 8000],
+    }
+}
+
+/// 座位排（从 start_index 起共 seat_count 个座位）
+pub fn seat_row(start_index: u32, seat_count: u32, row_label: &str) -> SeatRow {
+    SeatRow {
+        start_index,
+        seat_count,
+        row_label: row_label.to_string(),
+    }
+}
+
+/// 座位布局
+pub fn seat_layout(rows: Vec<SeatRow>) -> SeatLayoutAccount {
+    SeatLayoutAccount {
+        seat_status_map: Pubkey::default(),
+        rows,
+        created_at: 0,
+        updated_at: 0,
+        bump: 255,
+    }
+}
+
+/// 座位价格档位（从 start_index 起共 seat_count 个座位）
+pub fn price_tier(start_index: u32, seat_count: u32, price: SeatTierPrice) -> SeatPriceTier {
+    SeatPriceTier {
+        start_index,
+        seat_count,
+        price,
+    }
+}
+
+/// 座位价格档位账户
+pub fn price_tier_account(tiers: Vec<SeatPriceTier>) -> SeatPriceTierAccount {
+    SeatPriceTierAccount {
+        seat_status_map: Pubkey::default(),
+        tiers,
+        created_at: 0,
+        updated_at: 0,
+        bump: 255,
+    }
+}
+
+/// 票种（票价1 SOL，转售上限 max_resale_markup_bps、下限 min_resale_price_bps）
+pub fn ticket_type(max_resale_markup_bps: u16, min_resale_price_bps: u16) -> TicketTypeAccount {
+    TicketTypeAccount {
+        event: Pubkey::default(),
+        ticket_type_id: 0,
+        type_name: "VIP".to_string(),
+        initial_price: 1_000_000_000,
+        current_price: 1_000_000_000,
+        total_supply: 100,
+        sold_count: 0,
+        refunded_count: 0,
+        max_resale_royalty: 500,
+        is_fixed_price: true,
+        dynamic_pricing_rules_hash: None,
+        last_price_update: 0,
+        bump: 255,
+        refund_fee_bps: 0,
+        payment_mint: None,
+        max_per_wallet: 0,
+        max_resale_markup_bps,
+        min_resale_price_bps,
+    }
+}
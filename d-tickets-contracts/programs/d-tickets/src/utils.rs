@@ -0,0 +1,724 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{hash::hash, instruction::Instruction, program::invoke_signed};
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::{
+    metadata::{
+        self,
+        mpl_token_metadata::types::{Creator, DataV2},
+        CreateMasterEditionV3, CreateMetadataAccountsV3,
+    },
+    token::{self, MintTo, TokenAccount},
+    token_2022::spl_token_2022,
+    token_interface::{
+        self,
+        spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::TokenMetadata,
+        SetAuthority, TokenMetadataInitialize,
+    },
+};
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::InitializeExtraAccountMetaListInstruction;
+use crate::{
+    constants::*,
+    error::TicketError,
+    state::{EventAccount, OrganizerEarnings, PlatformAccount},
+};
+
+/// 按基点计算费用（向下取整）
+pub fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(TicketError::FeeCalculationError)?
+        / BASIS_POINTS_DIVISOR as u128;
+    u64::try_from(fee).map_err(|_| TicketError::FeeCalculationError.into())
+}
+
+/// 从签名者账户转账SOL（金额为0时跳过）
+pub fn transfer_lamports<'info>(
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    system_program::transfer(CpiContext::new(system_program, Transfer { from, to }), amount)
+}
+
+/// 为账户补足免租余额（已满足时跳过），用于预存托管账户和收款账户的租金
+pub fn fund_rent_exemption<'info>(
+    payer: AccountInfo<'info>,
+    account: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    let required_lamports = Rent::get()?
+        .minimum_balance(account.data_len())
+        .saturating_sub(account.lamports());
+    transfer_lamports(payer, account, system_program, required_lamports)
+}
+
+/// 创建由PDA签名的程序账户（用于 remaining_accounts 中需要初始化的账户）
+///
+/// PDA地址可预测，可能已被他人预先转入lamports；此时 create_account 会失败，
+/// 改为补足免租余额后分别 allocate 和 assign（与 Anchor 的 init 处理方式一致）
+pub fn create_pda_account<'info>(
+    payer: AccountInfo<'info>,
+    new_account: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent_lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = new_account.lamports();
+
+    if current_lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program,
+                system_program::CreateAccount { from: payer, to: new_account },
+                &[signer_seeds],
+            ),
+            rent_lamports,
+            space as u64,
+            owner,
+        );
+    }
+
+    transfer_lamports(
+        payer,
+        new_account.clone(),
+        system_program.clone(),
+        rent_lamports.saturating_sub(current_lamports),
+    )?;
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate { account_to_allocate: new_account.clone() },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program,
+            system_program::Assign { account_to_assign: new_account },
+            &[signer_seeds],
+        ),
+        owner,
+    )
+}
+
+/// 关闭本程序拥有的账户并将租金返还给接收账户（账户不存在时跳过）
+pub fn close_program_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    if account.owner != &crate::ID {
+        return Ok(());
+    }
+
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(TicketError::Overflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+/// 从活动托管账户（PDA）转出SOL
+///
+/// 转出后托管账户必须仍保持免租余额，保证后续的版税等小额入账不会因租金失败。
+/// 该余额由主办方在添加票种时预存（见 `fund_rent_exemption`），不占用活动收益，
+/// 因此 pending_amount 可以全额转出
+pub fn transfer_lamports_from_vault<'info>(
+    event_vault: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    event_key: &Pubkey,
+    vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let remaining = event_vault
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(TicketError::InsufficientBalance)?;
+    require!(
+        remaining >= Rent::get()?.minimum_balance(0),
+        TicketError::InsufficientBalance
+    );
+
+    let vault_seeds: &[&[u8]] = &[EARNINGS_SEED, event_key.as_ref(), VAULT_SEED, &[vault_bump]];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program,
+            Transfer { from: event_vault, to },
+            &[vault_seeds],
+        ),
+        amount,
+    )
+}
+
+/// 资金转移的一方：钱包账户，以及SPL代币结算时对应的代币账户
+pub struct Party<'info> {
+    pub wallet: AccountInfo<'info>,
+    pub token_account: Option<AccountInfo<'info>>,
+}
+
+/// 按活动结算币种转移资金（payment_mint 为 None 时使用SOL，否则使用该SPL代币）
+pub struct Settlement<'info> {
+    pub payment_mint: Option<Pubkey>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> Settlement<'info> {
+    pub fn new(
+        payment_mint: Option<Pubkey>,
+        system_program: AccountInfo<'info>,
+        token_program: AccountInfo<'info>,
+    ) -> Self {
+        Self { payment_mint, system_program, token_program }
+    }
+
+    /// 构造转账一方；SPL代币结算时校验代币账户的mint和持有者
+    pub fn party(
+        &self,
+        wallet: AccountInfo<'info>,
+        token_account: Option<&Account<'info, TokenAccount>>,
+    ) -> Result<Party<'info>> {
+        let token_account = match self.payment_mint {
+            None => None,
+            Some(mint) => {
+                let account = token_account.ok_or(TicketError::InvalidAccount)?;
+                require_keys_eq!(account.mint, mint, TicketError::InvalidPaymentMint);
+                require_keys_eq!(account.owner, wallet.key(), TicketError::InvalidAccount);
+                Some(account.to_account_info())
+            }
+        };
+        Ok(Party { wallet, token_account })
+    }
+
+    /// 校验付款方余额是否足够
+    pub fn require_balance(&self, payer: &Party<'info>, amount: u64) -> Result<()> {
+        let balance = match &payer.token_account {
+            None => payer.wallet.lamports(),
+            Some(account) => {
+                let data = account.try_borrow_data()?;
+                TokenAccount::try_deserialize(&mut &data[..])?.amount
+            }
+        };
+        require!(balance >= amount, TicketError::InsufficientPayment);
+        Ok(())
+    }
+
+    /// 由签名者付款（金额为0时跳过）
+    pub fn transfer(&self, from: &Party<'info>, to: &Party<'info>, amount: u64) -> Result<()> {
+        match (&from.token_account, &to.token_account) {
+            (None, None) => transfer_lamports(
+                from.wallet.clone(),
+                to.wallet.clone(),
+                self.system_program.clone(),
+                amount,
+            ),
+            (Some(from_token), Some(to_token)) => {
+                if amount == 0 {
+                    return Ok(());
+                }
+                token::transfer(
+                    CpiContext::new(
+                        self.token_program.clone(),
+                        token::Transfer {
+                            from: from_token.clone(),
+                            to: to_token.clone(),
+                            authority: from.wallet.clone(),
+                        },
+                    ),
+                    amount,
+                )
+            }
+            _ => err!(TicketError::InvalidAccount),
+        }
+    }
+
+    /// 从活动托管账户（PDA）付款
+    pub fn transfer_from_vault(
+        &self,
+        event_vault: &Party<'info>,
+        to: &Party<'info>,
+        event_key: &Pubkey,
+        vault_bump: u8,
+        amount: u64,
+    ) -> Result<()> {
+        match (&event_vault.token_account, &to.token_account) {
+            (None, None) => transfer_lamports_from_vault(
+                event_vault.wallet.clone(),
+                to.wallet.clone(),
+                self.system_program.clone(),
+                event_key,
+                vault_bump,
+                amount,
+            ),
+            (Some(vault_token), Some(to_token)) => {
+                if amount == 0 {
+                    return Ok(());
+                }
+                let vault_seeds: &[&[u8]] = &[EARNINGS_SEED, event_key.as_ref(), VAULT_SEED, &[vault_bump]];
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        self.token_program.clone(),
+                        token::Transfer {
+                            from: vault_token.clone(),
+                            to: to_token.clone(),
+                            authority: event_vault.wallet.clone(),
+                        },
+                        &[vault_seeds],
+                    ),
+                    amount,
+                )
+            }
+            _ => err!(TicketError::InvalidAccount),
+        }
+    }
+
+    /// 从程序托管账户（PDA）付款：SOL 直接从托管账户扣减，SPL代币由托管PDA签名转出
+    pub fn transfer_from_escrow(
+        &self,
+        escrow: &Party<'info>,
+        to: &Party<'info>,
+        escrow_seeds: &[&[u8]],
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        match (&escrow.token_account, &to.token_account) {
+            (None, None) => {
+                escrow.wallet.sub_lamports(amount)?;
+                to.wallet.add_lamports(amount)?;
+                Ok(())
+            }
+            (Some(escrow_token), Some(to_token)) => token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.clone(),
+                    token::Transfer {
+                        from: escrow_token.clone(),
+                        to: to_token.clone(),
+                        authority: escrow.wallet.clone(),
+                    },
+                    &[escrow_seeds],
+                ),
+                amount,
+            ),
+            _ => err!(TicketError::InvalidAccount),
+        }
+    }
+
+    /// 关闭托管PDA持有的SPL代币账户，租金返还指定账户（SOL结算时无需处理）
+    ///
+    /// 托管地址可预测，任何人都可以向其转入代币；关闭前按实际余额将剩余代币退还给 refund_to，
+    /// 保证托管账户总能清空关闭
+    pub fn close_escrow(
+        &self,
+        escrow: &Party<'info>,
+        refund_to: &Party<'info>,
+        destination: AccountInfo<'info>,
+        escrow_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let Some(escrow_token) = &escrow.token_account else {
+            return Ok(());
+        };
+        let remaining = {
+            let data = escrow_token.try_borrow_data()?;
+            TokenAccount::try_deserialize(&mut &data[..])?.amount
+        };
+        self.transfer_from_escrow(escrow, refund_to, escrow_seeds, remaining)?;
+        token::close_account(CpiContext::new_with_signer(
+            self.token_program.clone(),
+            token::CloseAccount {
+                account: escrow_token.clone(),
+                destination,
+                authority: escrow.wallet.clone(),
+            },
+            &[escrow_seeds],
+        ))
+    }
+}
+
+/// 一级市场售票结算所需的账户
+pub struct PrimarySaleAccounts<'a, 'info> {
+    pub buyer: AccountInfo<'info>,
+    pub buyer_payment_account: Option<&'a Account<'info, TokenAccount>>,
+    pub event_vault: AccountInfo<'info>,
+    pub vault_token_account: Option<&'a Account<'info, TokenAccount>>,
+    pub fee_recipient: AccountInfo<'info>,
+    pub fee_recipient_token_account: Option<&'a Account<'info, TokenAccount>>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// 一级市场售票结算：按活动结算币种收取总价，主办方收入转入活动托管账户，
+/// 平台手续费直接转给平台收款账户，并更新活动、主办方收益和平台统计
+///
+/// 返回主办方收入（总价扣除平台手续费）
+pub fn settle_primary_sale(
+    accounts: &PrimarySaleAccounts<'_, '_>,
+    total_price: u64,
+    quantity: u32,
+    event: &mut EventAccount,
+    earnings: &mut OrganizerEarnings,
+    platform: &mut PlatformAccount,
+) -> Result<u64> {
+    // 计算费用
+    let platform_fee = calculate_fee(total_price, platform.platform_fee_bps)?;
+    let organizer_amount = total_price
+        .checked_sub(platform_fee)
+        .ok_or(TicketError::FeeCalculationError)?;
+
+    let settlement = Settlement::new(
+        earnings.payment_mint,
+        accounts.system_program.clone(),
+        accounts.token_program.clone(),
+    );
+    let buyer = settlement.party(accounts.buyer.clone(), accounts.buyer_payment_account)?;
+    let event_vault = settlement.party(accounts.event_vault.clone(), accounts.vault_token_account)?;
+    let fee_recipient = settlement.party(
+        accounts.fee_recipient.clone(),
+        accounts.fee_recipient_token_account,
+    )?;
+
+    // 验证支付金额
+    settlement.require_balance(&buyer, total_price)?;
+
+    settlement.transfer(&buyer, &event_vault, organizer_amount)?;
+    settlement.transfer(&buyer, &fee_recipient, platform_fee)?;
+
+    // 更新统计数据
+    event.total_tickets_sold = event.total_tickets_sold
+        .checked_add(quantity)
+        .ok_or(TicketError::Overflow)?;
+    event.total_revenue = event.total_revenue
+        .checked_add(total_price)
+        .ok_or(TicketError::Overflow)?;
+    earnings.total_earnings = earnings.total_earnings
+        .checked_add(organizer_amount)
+        .ok_or(TicketError::Overflow)?;
+    earnings.pending_amount = earnings.pending_amount
+        .checked_add(organizer_amount)
+        .ok_or(TicketError::Overflow)?;
+    platform.total_platform_revenue = platform.total_platform_revenue
+        .checked_add(platform_fee)
+        .ok_or(TicketError::Overflow)?;
+    platform.total_transactions = platform.total_transactions
+        .checked_add(1)
+        .ok_or(TicketError::Overflow)?;
+
+    Ok(organizer_amount)
+}
+
+/// 铸造门票NFT所需的账户
+pub struct TicketNftAccounts<'info> {
+    pub mint: AccountInfo<'info>,
+    pub token_account: AccountInfo<'info>,
+    pub metadata: AccountInfo<'info>,
+    pub master_edition: AccountInfo<'info>,
+    pub mint_authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+/// 生成门票NFT名称（活动 + 票种 + 座位），按UTF-8字符边界截断到元数据长度上限
+pub fn ticket_nft_name(event_name: &str, ticket_type_name: &str, seat_number: &str) -> String {
+    let full_name = format!("{} {} {}", event_name, ticket_type_name, seat_number);
+    let mut end = full_name.len().min(MAX_NFT_NAME_LENGTH);
+    while !full_name.is_char_boundary(end) {
+        end -= 1;
+    }
+    full_name[..end].to_string()
+}
+
+/// 生成门票NFT元数据URI：活动元数据目录下按门票序号区分（{前缀}{活动哈希}/{序号}.json）
+pub fn ticket_metadata_uri(event_description_hash: &str, ticket_number: u32) -> String {
+    format!("{}{}/{}.json", NFT_METADATA_URI_PREFIX, event_description_hash, ticket_number)
+}
+
+/// 生成门票元数据哈希：对活动名称和门票序号取SHA-256（Base58编码不超过 IPFS_HASH_LENGTH）
+pub fn ticket_metadata_hash(event_name: &str, ticket_number: u32) -> String {
+    hash(format!("ticket-{}-{}", event_name, ticket_number).as_bytes()).to_string()
+}
+
+/// 铸造门票NFT并创建元数据和主版本
+///
+/// 主版本创建后铸造及冻结权限转移给 Token Metadata 的 edition PDA，供应量固定为1；
+/// 元数据中的版税比例使用票种设定的转售版税
+pub fn mint_ticket_nft<'info>(
+    accounts: &TicketNftAccounts<'info>,
+    mint_authority_bump: u8,
+    name: String,
+    uri: String,
+    organizer: Pubkey,
+    seller_fee_basis_points: u16,
+) -> Result<()> {
+    let mint_authority_seeds: &[&[u8]] = &[TICKET_MINT_AUTHORITY_SEED, &[mint_authority_bump]];
+    let signer_seeds = &[mint_authority_seeds];
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            MintTo {
+                mint: accounts.mint.clone(),
+                to: accounts.token_account.clone(),
+                authority: accounts.mint_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    metadata::create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            accounts.token_metadata_program.clone(),
+            CreateMetadataAccountsV3 {
+                metadata: accounts.metadata.clone(),
+                mint: accounts.mint.clone(),
+                mint_authority: accounts.mint_authority.clone(),
+                payer: accounts.payer.clone(),
+                update_authority: accounts.mint_authority.clone(),
+                system_program: accounts.system_program.clone(),
+                rent: accounts.rent.clone(),
+            },
+            signer_seeds,
+        ),
+        DataV2 {
+            name,
+            symbol: NFT_SYMBOL.to_string(),
+            uri,
+            seller_fee_basis_points,
+            creators: Some(vec![
+                Creator {
+                    address: accounts.mint_authority.key(),
+                    verified: true,
+                    share: 0,
+                },
+                Creator {
+                    address: organizer,
+                    verified: false,
+                    share: 100,
+                },
+            ]),
+            collection: None,
+            uses: None,
+        },
+        true,
+        true,
+        None,
+    )?;
+
+    metadata::create_master_edition_v3(
+        CpiContext::new_with_signer(
+            accounts.token_metadata_program.clone(),
+            CreateMasterEditionV3 {
+                edition: accounts.master_edition.clone(),
+                mint: accounts.mint.clone(),
+                update_authority: accounts.mint_authority.clone(),
+                mint_authority: accounts.mint_authority.clone(),
+                payer: accounts.payer.clone(),
+                metadata: accounts.metadata.clone(),
+                token_program: accounts.token_program.clone(),
+                system_program: accounts.system_program.clone(),
+                rent: accounts.rent.clone(),
+            },
+            signer_seeds,
+        ),
+        Some(0),
+    )
+}
+
+
+/// 铸造 Token-2022 门票NFT所需的账户
+pub struct TicketNft2022Accounts<'info> {
+    pub mint: AccountInfo<'info>,
+    pub token_account: AccountInfo<'info>,
+    pub mint_authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub extra_account_meta_list: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub transfer_hook_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// 铸造 Token-2022 门票NFT
+///
+/// 元数据直接写入mint账户（metadata pointer 指向mint自身），铸造1枚后撤销铸造权限，
+/// 并在转账钩子程序中初始化该mint的额外账户列表，之后的转账都会回调本程序
+pub fn mint_ticket_nft_2022<'info>(
+    accounts: &TicketNft2022Accounts<'info>,
+    mint_authority_bump: u8,
+    name: String,
+    uri: String,
+) -> Result<()> {
+    let mint_authority_seeds: &[&[u8]] = &[TICKET_MINT_AUTHORITY_SEED, &[mint_authority_bump]];
+    let signer_seeds = &[mint_authority_seeds];
+
+    // 为可变长度的元数据扩展补足免租余额，Token-2022 会在初始化时自行扩容
+    let token_metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey(accounts.mint_authority.key()),
+        mint: accounts.mint.key(),
+        name: name.clone(),
+        symbol: NFT_SYMBOL.to_string(),
+        uri: uri.clone(),
+        additional_metadata: vec![],
+    };
+    let data_len = accounts.mint.data_len() + token_metadata.tlv_size_of()?;
+    let required_lamports = Rent::get()?
+        .minimum_balance(data_len)
+        .saturating_sub(accounts.mint.lamports());
+    transfer_lamports(
+        accounts.payer.clone(),
+        accounts.mint.clone(),
+        accounts.system_program.clone(),
+        required_lamports,
+    )?;
+
+    token_interface::token_metadata_initialize(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            TokenMetadataInitialize {
+                program_id: accounts.token_program.clone(),
+                metadata: accounts.mint.clone(),
+                update_authority: accounts.mint_authority.clone(),
+                mint_authority: accounts.mint_authority.clone(),
+                mint: accounts.mint.clone(),
+            },
+            signer_seeds,
+        ),
+        name,
+        NFT_SYMBOL.to_string(),
+        uri,
+    )?;
+
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            token_interface::MintTo {
+                mint: accounts.mint.clone(),
+                to: accounts.token_account.clone(),
+                authority: accounts.mint_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    // 撤销铸造权限，供应量固定为1
+    token_interface::set_authority(
+        CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            SetAuthority {
+                current_authority: accounts.mint_authority.clone(),
+                account_or_mint: accounts.mint.clone(),
+            },
+            signer_seeds,
+        ),
+        spl_token_2022::instruction::AuthorityType::MintTokens,
+        None,
+    )?;
+
+    // 初始化转账钩子的额外账户列表（钩子程序要求由mint的转账钩子权限签名）
+    let initialize_ix = Instruction {
+        program_id: accounts.transfer_hook_program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.payer.key(), true),
+            AccountMeta::new(accounts.extra_account_meta_list.key(), false),
+            AccountMeta::new_readonly(accounts.mint.key(), false),
+            AccountMeta::new_readonly(accounts.mint_authority.key(), true),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data: InitializeExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE.to_vec(),
+    };
+    invoke_signed(
+        &initialize_ix,
+        &[
+            accounts.payer.clone(),
+            accounts.extra_account_meta_list.clone(),
+            accounts.mint.clone(),
+            accounts.mint_authority.clone(),
+            accounts.system_program.clone(),
+            accounts.transfer_hook_program.clone(),
+        ],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// 转移门票NFT（兼容 SPL Token 与 Token-2022）
+///
+/// Token-2022 门票的转账钩子所需的额外账户通过 additional_accounts 传入
+pub fn transfer_ticket_nft<'info>(
+    token_program: &AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    additional_accounts: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from,
+        mint,
+        to,
+        authority,
+        additional_accounts,
+        1,
+        0,
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calculate_fee_rounds_down_in_basis_points() {
+        assert_eq!(calculate_fee(1_000_000_000, 250).unwrap(), 25_000_000);
+        assert_eq!(calculate_fee(399, 250).unwrap(), 9);
+        assert_eq!(calculate_fee(1_000_000_000, 0).unwrap(), 0);
+        assert_eq!(calculate_fee(0, 1000).unwrap(), 0);
+    }
+
+    #[test]
+    fn calculate_fee_handles_full_u64_range() {
+        assert_eq!(calculate_fee(u64::MAX, BASIS_POINTS_DIVISOR as u16).unwrap(), u64::MAX);
+        assert_eq!(calculate_fee(u64::MAX, 5000).unwrap(), u64::MAX / 2);
+    }
+
+    #[test]
+    fn calculate_fee_rejects_results_above_u64() {
+        assert_eq!(
+            calculate_fee(u64::MAX, u16::MAX).unwrap_err(),
+            TicketError::FeeCalculationError.into()
+        );
+    }
+
+    #[test]
+    fn ticket_metadata_hash_fits_for_long_event_names() {
+        let event_name = "活".repeat(MAX_EVENT_NAME_LENGTH / 3);
+        let metadata_hash = ticket_metadata_hash(&event_name, u32::MAX);
+        assert!(metadata_hash.len() <= IPFS_HASH_LENGTH);
+        assert_ne!(metadata_hash, ticket_metadata_hash(&event_name, 1));
+    }
+}
@@ -28,6 +28,7 @@ describe("d-tickets venue-event integration", () => {
         .accounts({
           platform: platformPda,
           admin: organizer.publicKey,
+          feeRecipient: organizer.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .rpc();
@@ -36,6 +37,22 @@ describe("d-tickets venue-event integration", () => {
     }
   });
 
+  it("更新平台时仅传入收款账户不会更改收款地址", async () => {
+    const before = await program.account.platformAccount.fetch(platformPda);
+
+    await program.methods
+      .setupPlatform(null, null, null)
+      .accountsPartial({
+        platform: platformPda,
+        admin: organizer.publicKey,
+        feeRecipient: anchor.web3.Keypair.generate().publicKey,
+      })
+      .rpc();
+
+    const after = await program.account.platformAccount.fetch(platformPda);
+    expect(after.feeRecipient.toBase58()).to.equal(before.feeRecipient.toBase58());
+  });
+
   it("创建场馆", async () => {
     venueAccount = anchor.web3.Keypair.generate();
 
@@ -0,0 +1,278 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { DTickets } from "../target/types/d_tickets";
+import { expect } from "chai";
+
+// 座位状态分页（zero-copy）批量更新的计算单元基准测试
+describe("d-tickets seat status bitmap benchmark", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.DTickets as Program<DTickets>;
+  const organizer = provider.wallet;
+
+  const SEATS_PER_PAGE = 16000;
+  const BATCH_SIZE = 50;
+  const suffix = Date.now().toString().slice(-6);
+  const ticketTypeName = "看台票";
+
+  let platformPda: anchor.web3.PublicKey;
+  let eventPda: anchor.web3.PublicKey;
+  let earningsPda: anchor.web3.PublicKey;
+  let eventVaultPda: anchor.web3.PublicKey;
+  let ticketTypePda: anchor.web3.PublicKey;
+
+  const findPda = (seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const seatStatusMapPda = (areaId: string) =>
+    findPda([
+      Buffer.from("seat_status_map"),
+      eventPda.toBuffer(),
+      ticketTypePda.toBuffer(),
+      Buffer.from(areaId),
+    ]);
+
+  const seatStatusPagePda = (seatStatusMap: anchor.web3.PublicKey, pageIndex: number) => {
+    const pageIndexBytes = Buffer.alloc(2);
+    pageIndexBytes.writeUInt16LE(pageIndex);
+    return findPda([Buffer.from("seat_status_page"), seatStatusMap.toBuffer(), pageIndexBytes]);
+  };
+
+  // 创建座位状态映射及第0页分页账户
+  const createArea = async (areaId: string, totalSeats: number) => {
+    const seatStatusMap = seatStatusMapPda(areaId);
+    await program.methods
+      .createSeatStatusMap(ticketTypeName, areaId, "QmLayout", "QmIndexMap", totalSeats)
+      .accountsPartial({
+        creator: organizer.publicKey,
+        event: eventPda,
+        ticketType: ticketTypePda,
+        seatStatusMap,
+        platform: platformPda,
+      })
+      .rpc();
+    await program.methods
+      .createSeatStatusPage(ticketTypeName, areaId, 0)
+      .accountsPartial({
+        creator: organizer.publicKey,
+        event: eventPda,
+        ticketType: ticketTypePda,
+        seatStatusMap,
+        seatStatusPage: seatStatusPagePda(seatStatusMap, 0),
+        platform: platformPda,
+      })
+      .rpc();
+    return seatStatusMap;
+  };
+
+  // 主办方批量将50个座位标记为不可用，返回交易消耗的计算单元
+  const measureBatchUpdate = async (areaId: string, seatStatusMap: anchor.web3.PublicKey) => {
+    const seatUpdates = Array.from({ length: BATCH_SIZE }, (_, i) => ({
+      seatIndex: i,
+      newStatus: { unavailable: {} },
+      buyer: null,
+      seatInfo: null,
+    }));
+
+    const signature = await program.methods
+      .batchUpdateSeatStatus(ticketTypeName, areaId, seatUpdates)
+      .accountsPartial({
+        authority: organizer.publicKey,
+        event: eventPda,
+        ticketType: ticketTypePda,
+        seatStatusMap,
+        seatStatusPage: seatStatusPagePda(seatStatusMap, 0),
+        platform: platformPda,
+      })
+      .preInstructions([
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
+      ])
+      .rpc({ commitment: "confirmed" });
+
+    const transaction = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return transaction.meta.computeUnitsConsumed;
+  };
+
+  before(async () => {
+    platformPda = findPda([Buffer.from("platform")]);
+    try {
+      await program.methods
+        .setupPlatform(500, organizer.publicKey, false)
+        .accountsPartial({
+          platform: platformPda,
+          admin: organizer.publicKey,
+          feeRecipient: organizer.publicKey,
+        })
+        .rpc();
+    } catch (error) {
+      // 平台可能已存在，忽略错误
+    }
+
+    const venueName = `基准体育场${suffix}`;
+    const venuePda = findPda([
+      Buffer.from("venue"),
+      organizer.publicKey.toBuffer(),
+      Buffer.from(venueName),
+    ]);
+    await program.methods
+      .createVenue(venueName, "基准地址", 100000, "基准测试场馆", { stadium: {} }, "bench@example.com", null, null)
+      .accountsPartial({ creator: organizer.publicKey, venueAccount: venuePda, platform: platformPda })
+      .rpc();
+
+    const eventName = `基准赛事${suffix}`;
+    const now = Math.floor(Date.now() / 1000);
+    eventPda = findPda([Buffer.from("event"), organizer.publicKey.toBuffer(), Buffer.from(eventName)]);
+    earningsPda = findPda([Buffer.from("earnings"), eventPda.toBuffer()]);
+    eventVaultPda = findPda([Buffer.from("earnings"), eventPda.toBuffer(), Buffer.from("vault")]);
+    await program.methods
+      .createEvent(
+        eventName,
+        "QmBenchDescription",
+        "QmBenchPoster",
+        new anchor.BN(now + 86400 * 30),
+        new anchor.BN(now + 86400 * 30 + 7200),
+        new anchor.BN(now + 86400),
+        new anchor.BN(now + 86400 * 29),
+        null,
+        "体育",
+        "QmBenchPerformer",
+        "QmBenchContact",
+        "QmBenchRefund",
+        { fixedPrice: {} }
+      )
+      .accountsPartial({
+        event: eventPda,
+        earnings: earningsPda,
+        platform: platformPda,
+        venue: venuePda,
+        organizer: organizer.publicKey,
+      })
+      .rpc();
+
+    ticketTypePda = findPda([Buffer.from("ticket_type"), eventPda.toBuffer(), Buffer.from(ticketTypeName)]);
+    await program.methods
+      .addTicketType(ticketTypeName, new anchor.BN(10_000_000), 20000, 500, true, null)
+      .accountsPartial({
+        event: eventPda,
+        ticketType: ticketTypePda,
+        earnings: earningsPda,
+        platform: platformPda,
+        eventVault: eventVaultPda,
+        paymentMint: null,
+        vaultTokenAccount: null,
+        organizer: organizer.publicKey,
+      })
+      .rpc();
+  });
+
+  it("50个座位的批量更新计算单元不随位图大小增长", async () => {
+    const smallMap = await createArea("S", 100);
+    const fullMap = await createArea("F", SEATS_PER_PAGE);
+
+    const smallUnits = await measureBatchUpdate("S", smallMap);
+    const fullUnits = await measureBatchUpdate("F", fullMap);
+
+    console.log(`   100座位区域: ${smallUnits} CU`);
+    console.log(`   16000座位区域: ${fullUnits} CU`);
+
+    // 分页账户通过 AccountLoader 原地读写，8000字节的位图不参与序列化，
+    // 两个区域的计算单元应基本一致（Borsh 内联位图时满页区域需要额外反序列化和序列化8000字节）
+    expect(Math.abs(fullUnits - smallUnits)).to.be.lessThan(2000);
+
+    const page = await program.account.seatStatusPage.fetch(seatStatusPagePda(fullMap, 0));
+    expect(page.seatCount).to.equal(SEATS_PER_PAGE);
+    const seatStatusMap = await program.account.seatStatusMap.fetch(fullMap);
+    expect(seatStatusMap.totalSeats).to.equal(SEATS_PER_PAGE);
+  });
+
+  it("批量更新不能将座位标记为已售出", async () => {
+    const seatStatusMap = await createArea("X", 10);
+
+    try {
+      await program.methods
+        .batchUpdateSeatStatus(ticketTypeName, "X", [
+          { seatIndex: 0, newStatus: { sold: {} }, buyer: organizer.publicKey, seatInfo: null },
+        ])
+        .accountsPartial({
+          authority: organizer.publicKey,
+          event: eventPda,
+          ticketType: ticketTypePda,
+          seatStatusMap,
+          seatStatusPage: seatStatusPagePda(seatStatusMap, 0),
+          platform: platformPda,
+        })
+        .rpc();
+      expect.fail("批量更新不应售出座位");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidSeatStatus");
+    }
+  });
+
+  it("批量更新不能将座位标记为临时锁定", async () => {
+    const seatStatusMap = await createArea("L", 10);
+
+    try {
+      await program.methods
+        .batchUpdateSeatStatus(ticketTypeName, "L", [
+          { seatIndex: 0, newStatus: { tempLocked: {} }, buyer: null, seatInfo: null },
+        ])
+        .accountsPartial({
+          authority: organizer.publicKey,
+          event: eventPda,
+          ticketType: ticketTypePda,
+          seatStatusMap,
+          seatStatusPage: seatStatusPagePda(seatStatusMap, 0),
+          platform: platformPda,
+        })
+        .rpc();
+      expect.fail("临时锁定只能通过选座锁定创建");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidSeatStatus");
+    }
+  });
+
+  it("已创建的分页不能重复初始化", async () => {
+    const seatStatusMap = await createArea("R", 10);
+
+    try {
+      await program.methods
+        .createSeatStatusPage(ticketTypeName, "R", 0)
+        .accountsPartial({
+          creator: organizer.publicKey,
+          event: eventPda,
+          ticketType: ticketTypePda,
+          seatStatusMap,
+          seatStatusPage: seatStatusPagePda(seatStatusMap, 0),
+          platform: platformPda,
+        })
+        .rpc();
+      expect.fail("已创建的分页不应被重新初始化");
+    } catch (error) {
+      expect(error.toString()).to.contain("already in use");
+    }
+  });
+
+  it("删除座位状态映射时关闭全部分页账户", async () => {
+    const seatStatusMap = await createArea("D", 10);
+    const seatStatusPage = seatStatusPagePda(seatStatusMap, 0);
+
+    await program.methods
+      .deleteSeatStatusMap(ticketTypeName, "D")
+      .accountsPartial({
+        creator: organizer.publicKey,
+        event: eventPda,
+        ticketType: ticketTypePda,
+        seatStatusMap,
+        platform: platformPda,
+      })
+      .remainingAccounts([{ pubkey: seatStatusPage, isWritable: true, isSigner: false }])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(seatStatusMap)).to.be.null;
+    expect(await provider.connection.getAccountInfo(seatStatusPage)).to.be.null;
+  });
+});
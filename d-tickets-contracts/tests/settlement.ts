@@ -0,0 +1,465 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { DTickets } from "../target/types/d_tickets";
+import { expect } from "chai";
+
+// SOL结算流程：购票后提取收益、退票、出价成交与撤回
+describe("d-tickets SOL settlement", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.DTickets as Program<DTickets>;
+  const organizer = provider.wallet;
+  const connection = provider.connection;
+
+  const TOKEN_METADATA_PROGRAM_ID = new anchor.web3.PublicKey(
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+  );
+  const TICKET_PRICE = new anchor.BN(100_000_000); // 0.1 SOL
+  const suffix = Date.now().toString().slice(-6);
+  const ticketTypeName = "普通票";
+
+  const buyer = anchor.web3.Keypair.generate();
+  const bidder = anchor.web3.Keypair.generate();
+
+  let platformPda: anchor.web3.PublicKey;
+  let feeRecipient: anchor.web3.PublicKey;
+  let eventPda: anchor.web3.PublicKey;
+  let earningsPda: anchor.web3.PublicKey;
+  let eventVaultPda: anchor.web3.PublicKey;
+  let ticketTypePda: anchor.web3.PublicKey;
+  let heldTicketMint: anchor.web3.PublicKey;
+
+  const findPda = (seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const ticketPda = (ticketMint: anchor.web3.PublicKey) =>
+    findPda([Buffer.from("ticket"), ticketMint.toBuffer()]);
+
+  const offerPda = (offerBuyer: anchor.web3.PublicKey, offerId: anchor.BN) =>
+    findPda([
+      Buffer.from("marketplace_offer"),
+      offerBuyer.toBuffer(),
+      ticketTypePda.toBuffer(),
+      offerId.toArrayLike(Buffer, "le", 8),
+    ]);
+
+  const ticketTokenAccount = (ticketMint: anchor.web3.PublicKey, owner: anchor.web3.PublicKey) =>
+    anchor.utils.token.associatedAddress({ mint: ticketMint, owner });
+
+  const fundWallet = async (wallet: anchor.web3.PublicKey) => {
+    const signature = await connection.requestAirdrop(wallet, 10 * anchor.web3.LAMPORTS_PER_SOL);
+    await connection.confirmTransaction(signature, "confirmed");
+  };
+
+  const purchaseRecordPda = (owner: anchor.web3.PublicKey) =>
+    findPda([Buffer.from("purchase_record"), ticketTypePda.toBuffer(), owner.toBuffer()]);
+
+  // 票种下指定mint序号的门票mint及其元数据、主版本账户
+  const ticketMintAccounts = (index: number) => {
+    const mintIndex = Buffer.alloc(4);
+    mintIndex.writeUInt32LE(index);
+    const ticketMint = findPda([
+      Buffer.from("ticket_mint"),
+      eventPda.toBuffer(),
+      Buffer.from(ticketTypeName),
+      mintIndex,
+    ]);
+    const metadataSeeds = [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      ticketMint.toBuffer(),
+    ];
+    const [metadata] = anchor.web3.PublicKey.findProgramAddressSync(
+      metadataSeeds,
+      TOKEN_METADATA_PROGRAM_ID
+    );
+    const [masterEdition] = anchor.web3.PublicKey.findProgramAddressSync(
+      [...metadataSeeds, Buffer.from("edition")],
+      TOKEN_METADATA_PROGRAM_ID
+    );
+    return { ticketMint, metadata, masterEdition };
+  };
+
+  // 按票种当前的mint序号购买一张门票，返回门票mint
+  const purchaseTicket = async (owner: anchor.web3.Keypair) => {
+    const ticketType = await program.account.ticketTypeAccount.fetch(ticketTypePda);
+    const { ticketMint, metadata, masterEdition } = ticketMintAccounts(
+      ticketType.soldCount + ticketType.refundedCount
+    );
+
+    await program.methods
+      .purchaseTicket(ticketTypeName)
+      .accountsPartial({
+        event: eventPda,
+        ticketType: ticketTypePda,
+        earnings: earningsPda,
+        eventVault: eventVaultPda,
+        platform: platformPda,
+        feeRecipient,
+        ticketMint,
+        buyerTokenAccount: ticketTokenAccount(ticketMint, owner.publicKey),
+        metadata,
+        masterEdition,
+        ticket: ticketPda(ticketMint),
+        purchaseRecord: purchaseRecordPda(owner.publicKey),
+        buyer: owner.publicKey,
+        buyerPaymentAccount: null,
+        vaultTokenAccount: null,
+        feeRecipientTokenAccount: null,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .preInstructions([
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+      ])
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    return ticketMint;
+  };
+
+  const makeOffer = async (offerId: anchor.BN, price: anchor.BN) => {
+    await program.methods
+      .makeOffer(offerId, null, price, null)
+      .accountsPartial({
+        ticketType: ticketTypePda,
+        event: eventPda,
+        earnings: earningsPda,
+        offer: offerPda(bidder.publicKey, offerId),
+        escrowTokenAccount: null,
+        buyerPaymentAccount: null,
+        platform: platformPda,
+        buyer: bidder.publicKey,
+      })
+      .signers([bidder])
+      .rpc({ commitment: "confirmed" });
+  };
+
+  before(async () => {
+    platformPda = findPda([Buffer.from("platform")]);
+    try {
+      await program.methods
+        .setupPlatform(500, organizer.publicKey, false)
+        .accountsPartial({
+          platform: platformPda,
+          admin: organizer.publicKey,
+          feeRecipient: organizer.publicKey,
+        })
+        .rpc();
+    } catch (error) {
+      // 平台可能已存在，忽略错误
+    }
+    feeRecipient = (await program.account.platformAccount.fetch(platformPda)).feeRecipient;
+
+    await fundWallet(buyer.publicKey);
+    await fundWallet(bidder.publicKey);
+
+    const venueName = `结算测试场馆${suffix}`;
+    const venuePda = findPda([
+      Buffer.from("venue"),
+      organizer.publicKey.toBuffer(),
+      Buffer.from(venueName),
+    ]);
+    await program.methods
+      .createVenue(
+        venueName,
+        "结算测试地址",
+        1000,
+        "结算测试场馆",
+        { concert: {} },
+        "settlement@example.com",
+        null,
+        null
+      )
+      .accountsPartial({ creator: organizer.publicKey, venueAccount: venuePda, platform: platformPda })
+      .rpc();
+
+    // 售票已开始，活动30天后开始（允许退票和出价）
+    const eventName = `结算测试活动${suffix}`;
+    const now = Math.floor(Date.now() / 1000);
+    eventPda = findPda([Buffer.from("event"), organizer.publicKey.toBuffer(), Buffer.from(eventName)]);
+    earningsPda = findPda([Buffer.from("earnings"), eventPda.toBuffer()]);
+    eventVaultPda = findPda([Buffer.from("earnings"), eventPda.toBuffer(), Buffer.from("vault")]);
+    await program.methods
+      .createEvent(
+        eventName,
+        "QmSettlementDescription",
+        "QmSettlementPoster",
+        new anchor.BN(now + 86400 * 30),
+        new anchor.BN(now + 86400 * 30 + 7200),
+        new anchor.BN(now - 60),
+        new anchor.BN(now + 86400 * 29),
+        null,
+        "音乐会",
+        "QmSettlementPerformer",
+        "QmSettlementContact",
+        "QmSettlementRefund",
+        { fixedPrice: {} }
+      )
+      .accountsPartial({
+        event: eventPda,
+        earnings: earningsPda,
+        platform: platformPda,
+        venue: venuePda,
+        organizer: organizer.publicKey,
+      })
+      .rpc();
+
+    ticketTypePda = findPda([Buffer.from("ticket_type"), eventPda.toBuffer(), Buffer.from(ticketTypeName)]);
+    await program.methods
+      .addTicketType(ticketTypeName, TICKET_PRICE, 100, 500, true, null)
+      .accountsPartial({
+        event: eventPda,
+        ticketType: ticketTypePda,
+        earnings: earningsPda,
+        platform: platformPda,
+        eventVault: eventVaultPda,
+        paymentMint: null,
+        vaultTokenAccount: null,
+        organizer: organizer.publicKey,
+      })
+      .rpc();
+  });
+
+  it("购票后主办方可全额提取待提取收益", async () => {
+    heldTicketMint = await purchaseTicket(buyer);
+
+    const platform = await program.account.platformAccount.fetch(platformPda);
+    const platformFee = TICKET_PRICE.muln(platform.platformFeeBps).divn(10_000);
+    const earnings = await program.account.organizerEarnings.fetch(earningsPda);
+    expect(earnings.pendingAmount.toString()).to.equal(TICKET_PRICE.sub(platformFee).toString());
+
+    const rentReserve = await connection.getMinimumBalanceForRentExemption(0);
+    const vaultBefore = await connection.getBalance(eventVaultPda, "confirmed");
+    expect(vaultBefore).to.equal(rentReserve + earnings.pendingAmount.toNumber());
+
+    await program.methods
+      .withdrawProceeds(earnings.pendingAmount)
+      .accountsPartial({
+        event: eventPda,
+        earnings: earningsPda,
+        eventVault: eventVaultPda,
+        payoutDestination: organizer.publicKey,
+        payoutTokenAccount: null,
+        vaultTokenAccount: null,
+        organizer: organizer.publicKey,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    // 托管账户只保留免租余额，收益全部提取
+    const earningsAfter = await program.account.organizerEarnings.fetch(earningsPda);
+    expect(earningsAfter.pendingAmount.toNumber()).to.equal(0);
+    expect(earningsAfter.withdrawnAmount.toString()).to.equal(earnings.pendingAmount.toString());
+    expect(await connection.getBalance(eventVaultPda, "confirmed")).to.equal(rentReserve);
+  });
+
+  it("退票按该门票存入托管的金额退款", async () => {
+    const ticketMint = await purchaseTicket(buyer);
+    const ticket = await program.account.ticketAccount.fetch(ticketPda(ticketMint));
+    const ticketType = await program.account.ticketTypeAccount.fetch(ticketTypePda);
+    const refundFee = ticket.originalPrice.muln(ticketType.refundFeeBps).divn(10_000);
+    const refundAmount = ticket.organizerAmount.sub(refundFee);
+    const tokenAccountRent = await connection.getMinimumBalanceForRentExemption(165);
+
+    const buyerBefore = await connection.getBalance(buyer.publicKey, "confirmed");
+    const earningsBefore = await program.account.organizerEarnings.fetch(earningsPda);
+    const eventBefore = await program.account.eventAccount.fetch(eventPda);
+
+    await program.methods
+      .refundTicket()
+      .accountsPartial({
+        event: eventPda,
+        ticketType: ticketTypePda,
+        ticket: ticketPda(ticketMint),
+        earnings: earningsPda,
+        eventVault: eventVaultPda,
+        ticketMint,
+        holderTokenAccount: ticketTokenAccount(ticketMint, buyer.publicKey),
+        refundRequester: buyer.publicKey,
+        seatStatusMap: null,
+        seatStatusPage: null,
+        requesterPaymentAccount: null,
+        vaultTokenAccount: null,
+        ticketTokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        platform: platformPda,
+      })
+      .signers([buyer])
+      .rpc({ commitment: "confirmed" });
+
+    // 买家收到退款及关闭门票代币账户返还的租金（交易费由提供者钱包支付）
+    const buyerAfter = await connection.getBalance(buyer.publicKey, "confirmed");
+    expect(buyerAfter - buyerBefore).to.equal(refundAmount.toNumber() + tokenAccountRent);
+
+    const refunded = await program.account.ticketAccount.fetch(ticketPda(ticketMint));
+    expect(refunded.currentStatus).to.deep.equal({ refunded: {} });
+    const earningsAfter = await program.account.organizerEarnings.fetch(earningsPda);
+    expect(earningsBefore.pendingAmount.sub(earningsAfter.pendingAmount).toString()).to.equal(
+      refundAmount.toString()
+    );
+    // 活动收入按购票时计入的票价扣回
+    const eventAfter = await program.account.eventAccount.fetch(eventPda);
+    expect(eventBefore.totalRevenue.sub(eventAfter.totalRevenue).toString()).to.equal(
+      ticket.originalPrice.toString()
+    );
+  });
+
+  it("接受出价后门票转给出价者，托管金额按费用分配", async () => {
+    const offerId = new anchor.BN(1);
+    const offer = offerPda(bidder.publicKey, offerId);
+    await makeOffer(offerId, TICKET_PRICE);
+
+    const platform = await program.account.platformAccount.fetch(platformPda);
+    const ticketType = await program.account.ticketTypeAccount.fetch(ticketTypePda);
+    const platformFee = TICKET_PRICE.muln(platform.platformFeeBps).divn(10_000);
+    const royaltyFee = TICKET_PRICE.muln(ticketType.maxResaleRoyalty).divn(10_000);
+    const sellerAmount = TICKET_PRICE.sub(platformFee).sub(royaltyFee);
+    const tokenAccountRent = await connection.getMinimumBalanceForRentExemption(165);
+
+    const sellerBefore = await connection.getBalance(buyer.publicKey, "confirmed");
+    const vaultBefore = await connection.getBalance(eventVaultPda, "confirmed");
+
+    await program.methods
+      .acceptOffer()
+      .accountsPartial({
+        offer,
+        ticket: ticketPda(heldTicketMint),
+        ticketType: ticketTypePda,
+        earnings: earningsPda,
+        eventVault: eventVaultPda,
+        platform: platformPda,
+        feeRecipient,
+        buyer: bidder.publicKey,
+        ticketMint: heldTicketMint,
+        sellerTokenAccount: ticketTokenAccount(heldTicketMint, buyer.publicKey),
+        buyerTokenAccount: ticketTokenAccount(heldTicketMint, bidder.publicKey),
+        seller: buyer.publicKey,
+        escrowTokenAccount: null,
+        buyerPaymentAccount: null,
+        sellerPaymentAccount: null,
+        vaultTokenAccount: null,
+        feeRecipientTokenAccount: null,
+        ticketTokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      })
+      .signers([buyer])
+      .rpc({ commitment: "confirmed" });
+
+    const ticket = await program.account.ticketAccount.fetch(ticketPda(heldTicketMint));
+    expect(ticket.currentOwner.toString()).to.equal(bidder.publicKey.toString());
+    const bidderTicket = await connection.getTokenAccountBalance(
+      ticketTokenAccount(heldTicketMint, bidder.publicKey),
+      "confirmed"
+    );
+    expect(bidderTicket.value.amount).to.equal("1");
+    expect(await connection.getAccountInfo(offer, "confirmed")).to.be.null;
+
+    // 卖家收到扣除费用后的金额，并支付买家门票代币账户的租金
+    const sellerAfter = await connection.getBalance(buyer.publicKey, "confirmed");
+    expect(sellerAfter - sellerBefore).to.equal(sellerAmount.toNumber() - tokenAccountRent);
+    const vaultAfter = await connection.getBalance(eventVaultPda, "confirmed");
+    expect(vaultAfter - vaultBefore).to.equal(royaltyFee.toNumber());
+  });
+
+  it("撤回出价退还托管金额和租金", async () => {
+    const offerId = new anchor.BN(2);
+    const offer = offerPda(bidder.publicKey, offerId);
+    const bidderBefore = await connection.getBalance(bidder.publicKey, "confirmed");
+
+    await makeOffer(offerId, TICKET_PRICE);
+    const escrowed = await connection.getBalance(offer, "confirmed");
+    expect(bidderBefore - (await connection.getBalance(bidder.publicKey, "confirmed"))).to.equal(escrowed);
+
+    await program.methods
+      .withdrawOffer()
+      .accountsPartial({
+        offer,
+        escrowTokenAccount: null,
+        buyerPaymentAccount: null,
+        buyer: bidder.publicKey,
+        caller: bidder.publicKey,
+      })
+      .signers([bidder])
+      .rpc({ commitment: "confirmed" });
+
+    expect(await connection.getAccountInfo(offer, "confirmed")).to.be.null;
+    expect(await connection.getBalance(bidder.publicKey, "confirmed")).to.equal(bidderBefore);
+  });
+
+  it("一次购买多张门票，每张门票记录平均分摊的托管金额", async () => {
+    const quantity = 3;
+    const platform = await program.account.platformAccount.fetch(platformPda);
+    const ticketType = await program.account.ticketTypeAccount.fetch(ticketTypePda);
+    const totalPrice = TICKET_PRICE.muln(quantity);
+    const organizerAmount = totalPrice.sub(totalPrice.muln(platform.platformFeeBps).divn(10_000));
+    const earningsBefore = await program.account.organizerEarnings.fetch(earningsPda);
+
+    const firstIndex = ticketType.soldCount + ticketType.refundedCount;
+    const ticketMints = Array.from({ length: quantity }, (_, i) => ticketMintAccounts(firstIndex + i));
+    const remainingAccounts = ticketMints.flatMap(({ ticketMint, metadata, masterEdition }) =>
+      [ticketMint, ticketTokenAccount(ticketMint, buyer.publicKey), metadata, masterEdition, ticketPda(ticketMint)].map(
+        (pubkey) => ({ pubkey, isWritable: true, isSigner: false })
+      )
+    );
+
+    await program.methods
+      .purchaseTickets(ticketTypeName, quantity)
+      .accountsPartial({
+        event: eventPda,
+        ticketType: ticketTypePda,
+        earnings: earningsPda,
+        eventVault: eventVaultPda,
+        platform: platformPda,
+        feeRecipient,
+        purchaseRecord: purchaseRecordPda(buyer.publicKey),
+        buyer: buyer.publicKey,
+        buyerPaymentAccount: null,
+        vaultTokenAccount: null,
+        feeRecipientTokenAccount: null,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .preInstructions([
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
+      ])
+      .signers([buyer])
+      .rpc({ commitment: "confirmed" });
+
+    const ticketTypeAfter = await program.account.ticketTypeAccount.fetch(ticketTypePda);
+    expect(ticketTypeAfter.soldCount).to.equal(ticketType.soldCount + quantity);
+    const earningsAfter = await program.account.organizerEarnings.fetch(earningsPda);
+    expect(earningsAfter.pendingAmount.sub(earningsBefore.pendingAmount).toString()).to.equal(
+      organizerAmount.toString()
+    );
+    for (const { ticketMint } of ticketMints) {
+      const ticket = await program.account.ticketAccount.fetch(ticketPda(ticketMint));
+      expect(ticket.currentOwner.toString()).to.equal(buyer.publicKey.toString());
+      expect(ticket.organizerAmount.toString()).to.equal(organizerAmount.divn(quantity).toString());
+      expect(ticket.metadataHash.length).to.be.at.most(46);
+    }
+  });
+
+  it("单笔购买数量超过上限时拒绝", async () => {
+    try {
+      await program.methods
+        .purchaseTickets(ticketTypeName, 4)
+        .accountsPartial({
+          event: eventPda,
+          ticketType: ticketTypePda,
+          earnings: earningsPda,
+          eventVault: eventVaultPda,
+          platform: platformPda,
+          feeRecipient,
+          purchaseRecord: purchaseRecordPda(buyer.publicKey),
+          buyer: buyer.publicKey,
+          buyerPaymentAccount: null,
+          vaultTokenAccount: null,
+          feeRecipientTokenAccount: null,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .signers([buyer])
+        .rpc({ commitment: "confirmed" });
+      expect.fail("购买数量超过上限时应失败");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("ExceedsPurchaseLimit");
+    }
+  });
+});